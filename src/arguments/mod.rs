@@ -1,93 +1,615 @@
-use rust_decimal::{prelude::Zero, Decimal};
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::output::{OutputFormat, Records, SortKey, SortOrder};
+use crate::provider::ProviderKind;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
 use core::fmt;
+use std::path::PathBuf;
 use std::{env, str::FromStr};
 
-#[derive(Debug)]
-pub enum ArgsError 
+const PROGRAM: &str = "exchange-rate-api";
+
+/// Exit status of a run that failed to fetch or save data for any other reason.
+pub const EXIT_FAILURE: u8 = 1;
+/// Exit status of a command line that could not be parsed.
+pub const EXIT_USAGE: u8 = 2;
+/// Exit status when the plan quota or the local rate limit is used up.
+pub const EXIT_QUOTA_REACHED: u8 = 3;
+/// Exit status when the API key is invalid or its account inactive.
+pub const EXIT_UNAUTHORIZED: u8 = 4;
+/// Exit status when the provider could not be reached or failed on its side.
+pub const EXIT_UNAVAILABLE: u8 = 5;
+/// Exit status when a currency, amount or input file is not accepted.
+pub const EXIT_INVALID_REQUEST: u8 = 6;
+
+/// Exit status of a run that failed with `error`, so that scripts can tell a retry
+/// later from a configuration or input problem.
+pub fn exit_code(error: &ExchangeRateError) -> u8
+{
+    match error {
+        ExchangeRateError::QuotaReached | ExchangeRateError::RateLimited => EXIT_QUOTA_REACHED,
+        ExchangeRateError::InvalidKey | ExchangeRateError::InactiveAccount => EXIT_UNAUTHORIZED,
+        ExchangeRateError::Transport(_) | ExchangeRateError::Server(_) => EXIT_UNAVAILABLE,
+        ExchangeRateError::UnsupportedCode
+        | ExchangeRateError::MalformedRequest
+        | ExchangeRateError::InvalidCurrencyCode(_)
+        | ExchangeRateError::AmountOverflow
+        | ExchangeRateError::InvalidInput(_) => EXIT_INVALID_REQUEST,
+        _ => EXIT_FAILURE,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command
 {
-    HelpRequested,
-    InvalidNumberOfArguments,
-    ParseError,
+    Convert { source: CurrencyCode, target: CurrencyCode, amount: Decimal },
+    /// Converts into each of `targets`, or into every currency when `None`, from one table of rates.
+    ConvertMany { amount: Decimal, source: CurrencyCode, targets: Option<Vec<CurrencyCode>> },
+    Rates { source: CurrencyCode },
+    Codes,
+    Quota,
+    Enriched { source: CurrencyCode, target: CurrencyCode },
+    Historical { source: CurrencyCode, date: NaiveDate, amount: Option<Decimal> },
+    Snapshot { output: PathBuf, bases: Vec<CurrencyCode> },
+    /// Conversions read from a CSV or JSON lines file, or from stdin when `input` is `-`.
+    Batch { input: PathBuf },
+    /// Rates recorded in $RATE_HISTORY_DB: all of them, or the one in force `at` a time.
+    History { base: CurrencyCode, target: CurrencyCode, at: Option<DateTime<Utc>> },
 }
 
-impl fmt::Display for ArgsError 
+impl Command
 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result 
+    /// The fixed columns of the command's results.
+    pub fn columns(&self) -> &'static [&'static str]
     {
-        match *self 
-        {
-            ArgsError::HelpRequested => write!(f,"{}", 
-"-----------------------------------------------------
+        match self {
+            Command::Convert { .. } | Command::ConvertMany { .. } => Records::CONVERSION_COLUMNS,
+            Command::Rates { .. } => Records::RATES_COLUMNS,
+            Command::Codes => Records::CODES_COLUMNS,
+            Command::Quota => Records::QUOTA_COLUMNS,
+            Command::Enriched { .. } => Records::ENRICHED_COLUMNS,
+            Command::Historical { .. } => Records::HISTORICAL_COLUMNS,
+            Command::Snapshot { .. } => Records::SNAPSHOT_COLUMNS,
+            Command::Batch { .. } => Records::BATCH_COLUMNS,
+            Command::History { .. } => Records::HISTORY_COLUMNS,
+        }
+    }
+}
 
-Default mode:
-./exchange-rate-api <source> <target> <decimal_value>
-Provide three arguments: 
-<source> - the currency you are converting from,
+/// Options that apply to every command and may appear anywhere on the command line.
+#[derive(Debug, Default, PartialEq)]
+pub struct GlobalOptions
+{
+    /// Serve everything from a saved rates response or snapshot instead of the API.
+    pub offline: Option<PathBuf>,
+    /// Rate providers in priority order; $RATE_PROVIDER when not given.
+    pub providers: Option<Vec<ProviderKind>>,
+    /// Query all providers and flag spreads above this fraction; $RATE_DIVERGENCE_THRESHOLD when not given.
+    pub divergence_threshold: Option<Decimal>,
+    /// Format of the results written to standard output.
+    pub output: OutputFormat,
+    pub sort: Option<SortOrder>,
+    /// Keep the rows of these currencies only.
+    pub filter: Option<Vec<CurrencyCode>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Cli
+{
+    pub options: GlobalOptions,
+    pub command: Command,
+}
+
+struct CommandSpec
+{
+    name: &'static str,
+    arguments: &'static str,
+    summary: &'static str,
+    details: &'static str,
+    example: &'static str,
+}
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "convert",
+        arguments: "<source> <target> <amount> | <amount> <source> --to <code>[,<code>...]|all",
+        summary: "Convert an amount from one currency to another, or to many",
+        details: "<source> - the currency you are converting from,
 <target> - the currency you are converting to,
-<decimal_value> - the amount to be converted.
+<amount> - the amount to be converted.
+With --to, the amount is converted into every listed currency, or with `all` into every
+supported currency, from a single request for the latest rates.",
+        example: "convert 100 USD --to EUR,GBP,JPY",
+    },
+    CommandSpec {
+        name: "rates",
+        arguments: "<source>",
+        summary: "List the current exchange rates against a single currency",
+        details: "<source> - the currency the rates are quoted against.",
+        example: "rates USD",
+    },
+    CommandSpec {
+        name: "codes",
+        arguments: "",
+        summary: "List all available currencies",
+        details: "Lists the code and name of every supported currency.",
+        example: "codes",
+    },
+    CommandSpec {
+        name: "quota",
+        arguments: "",
+        summary: "Show the plan quota and the requests remaining",
+        details: "Shows the plan quota, the requests remaining and the day of month the quota refreshes.
+Requires $API_KEY.",
+        example: "quota",
+    },
+    CommandSpec {
+        name: "enriched",
+        arguments: "<source> <target>",
+        summary: "Show an exchange rate with the target currency's details",
+        details: "Shows the exchange rate together with the target currency's name, symbol, locale and flag.
+Requires $API_KEY.",
+        example: "enriched USD JPY",
+    },
+    CommandSpec {
+        name: "historical",
+        arguments: "<source> <YYYY-MM-DD> [<amount>]",
+        summary: "List the exchange rates on a past date",
+        details: "Lists the exchange rates against <source> on a past date, or with <amount>,
+that amount converted into every currency. Requires $API_KEY.",
+        example: "historical USD 2024-03-01 100",
+    },
+    CommandSpec {
+        name: "snapshot",
+        arguments: "<file> <base> [<base>...]",
+        summary: "Save the latest exchange rates to a JSON file",
+        details: "Saves the latest exchange rates against every <base> to a versioned JSON file,
+for later use with --offline. The file is replaced atomically.",
+        example: "snapshot rates.json USD EUR",
+    },
+    CommandSpec {
+        name: "batch",
+        arguments: "<file>|-",
+        summary: "Convert every row of a CSV or JSON lines file",
+        details: "Converts every row of a CSV file with a header row, or of a file of JSON objects one per line,
+read from standard input when <file> is -. Each row has an amount, a from and a to currency
+and optionally the date of the rates to use (YYYY-MM-DD, requires $API_KEY). The rates against
+each currency and date are fetched once. Every input column is written back, followed by the
+rate, result, last_update and error columns; rows that cannot be converted are reported in the
+error column while the others are converted.",
+        example: "batch transactions.csv --output csv",
+    },
+    CommandSpec {
+        name: "history",
+        arguments: "<source> <target> [<YYYY-MM-DD>|<YYYY-MM-DDTHH:MM:SSZ>]",
+        summary: "List the exchange rates recorded in $RATE_HISTORY_DB",
+        details: "Lists every recorded exchange rate of the pair, or the one in force at the given time
+(the end of the day if only a date is given).",
+        example: "history USD PLN 2024-03-22T12:00:00Z",
+    },
+];
 
-Example: ./exchange-rate-api USD PLN 100.50
+fn command_spec(name: &str) -> Option<&'static CommandSpec>
+{
+    COMMANDS.iter().find(|command_spec| command_spec.name == name)
+}
 
------------------------------------------------------
+#[derive(Debug)]
+pub enum ArgsError
+{
+    /// General help, or the help of the named command.
+    HelpRequested(Option<&'static str>),
+    VersionRequested,
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownOption(String),
+    MissingOptionValue(String),
+    /// Wrong number of arguments for the named command.
+    InvalidArguments(&'static str),
+    InvalidAmount(String),
+    InvalidCurrencyCode(String),
+    InvalidDate(String),
+    InvalidProvider(String),
+    InvalidThreshold(String),
+    InvalidOutputFormat(String),
+    InvalidSortOrder(String),
+    /// The named command's results have no such column to sort or filter by.
+    MissingColumn(&'static str, &'static str),
+}
 
-List all available currencies:
-./exchange-rate-api --codes
+impl ArgsError
+{
+    /// Help and version requests are not failures; everything else is a usage error.
+    pub fn exit_code(&self) -> u8
+    {
+        match self {
+            ArgsError::HelpRequested(_) | ArgsError::VersionRequested => 0,
+            _ => EXIT_USAGE,
+        }
+    }
+}
 
------------------------------------------------------
+impl fmt::Display for ArgsError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            ArgsError::HelpRequested(None) => {
+                writeln!(f, "Usage: {} [OPTIONS] <COMMAND> [ARGS]", PROGRAM)?;
+                writeln!(f)?;
+                writeln!(f, "Commands:")?;
+                for command_spec in COMMANDS {
+                    writeln!(f, "  {:<12}{}", command_spec.name, command_spec.summary)?;
+                }
+                writeln!(f, "  {:<12}Show the help of a command", "help")?;
+                write!(f, "
+Options:
+  --offline <file>                   Serve rates from a file saved by `snapshot` or
+                                     from a saved latest rates response
+  --provider <name>[,<name>...]      Rate providers in priority order: exchangerate-api,
+                                     ecb or frankfurter (default: $RATE_PROVIDER)
+  --divergence-threshold <fraction>  Query all providers and warn when their rates differ
+                                     by more than this, e.g. 0.01 for 1%
+                                     (default: $RATE_DIVERGENCE_THRESHOLD)
+  -o, --output <format>              Write the results as table, json, csv or tsv
+                                     (default: table)
+  --sort <column>[:asc|:desc]        Sort the rows by code, name or rate
+  --filter <code>[,<code>...]        Show the rows of these currencies only
+  -h, --help                         Show this help, or the help of a command
+  -V, --version                      Show the version
 
-List all available currencies and the current exchange rates against a single currency:
-./exchange-rate-api <source>
+Environment:
+  API_KEY          Key to https://www.exchangerate-api.com; without it the keyless
+                   open access endpoint is used
+  API_CACHE_DIR    Keep responses on disk until the API publishes its next update
+  RATE_HISTORY_DB  Record every fetched table of rates in this SQLite database
 
-Provide one argument:
-<source> - the currency you are converting from,
-Example: ./exchange-rate-api USD
+Exit status:
+  0  Success
+  1  The rates could not be fetched or saved for any other reason
+  2  The command line is invalid
+  3  The request quota or the local rate limit is used up
+  4  The API key is invalid or its account inactive
+  5  The provider could not be reached or failed on its side
+  6  A currency, amount or input file was not accepted
 
------------------------------------------------------
-"),
-    
-            ArgsError::InvalidNumberOfArguments => write!(f, "{}",
-"Invalid number of arguments! Use --help to display help message.
-"),
-            ArgsError::ParseError => write!(f, "{}",
-"Error with parsing 3th argument. Please enter floating number
-"),
+Run '{} help <command>' for more information on a command.
+", PROGRAM)
+            },
+            ArgsError::HelpRequested(Some(name)) => {
+                let command_spec = command_spec(name).expect("help is only requested for known commands");
+                writeln!(f, "{}", command_spec.summary)?;
+                writeln!(f)?;
+                writeln!(f, "Usage: {} [OPTIONS] {} {}", PROGRAM, command_spec.name, command_spec.arguments)?;
+                writeln!(f)?;
+                writeln!(f, "{}", command_spec.details)?;
+                writeln!(f)?;
+                writeln!(f, "Example: {} {}", PROGRAM, command_spec.example)
+            },
+            ArgsError::VersionRequested => writeln!(f, "{} {}", PROGRAM, env!("CARGO_PKG_VERSION")),
+            ArgsError::MissingCommand => writeln!(f,
+"Missing command. Use --help to display help message."),
+            ArgsError::UnknownCommand(name) => writeln!(f,
+"Unknown command: {}. Use --help to list the commands.", name),
+            ArgsError::UnknownOption(option) => writeln!(f,
+"Unknown option: {}. Use --help to list the options.", option),
+            ArgsError::MissingOptionValue(option) => writeln!(f,
+"Missing value for {}. Use --help to display help message.", option),
+            ArgsError::InvalidArguments(name) => {
+                let command_spec = command_spec(name).expect("commands are validated before their arguments");
+                writeln!(f, "Usage: {} [OPTIONS] {} {}", PROGRAM, command_spec.name, command_spec.arguments)?;
+                writeln!(f, "Run '{} help {}' for more information.", PROGRAM, command_spec.name)
+            },
+            ArgsError::InvalidAmount(amount) => writeln!(f,
+"Invalid amount: {}. Please enter a decimal number", amount),
+            ArgsError::InvalidCurrencyCode(code) => writeln!(f,
+"Invalid currency code: {}. Use the codes command to list supported currencies.", code),
+            ArgsError::InvalidDate(date) => writeln!(f,
+"Invalid date: {}. Please enter the date as YYYY-MM-DD", date),
+            ArgsError::InvalidProvider(name) => writeln!(f,
+"Unknown rate provider: {}. Choose from exchangerate-api, ecb and frankfurter.", name),
+            ArgsError::InvalidThreshold(threshold) => writeln!(f,
+"Invalid divergence threshold: {}. Please enter a fraction such as 0.01", threshold),
+            ArgsError::InvalidOutputFormat(format) => writeln!(f,
+"Unknown output format: {}. Choose from table, json, csv and tsv.", format),
+            ArgsError::InvalidSortOrder(order) => writeln!(f,
+"Invalid sort order: {}. Choose code, name or rate, optionally followed by :asc or :desc", order),
+            ArgsError::MissingColumn(name, column) => writeln!(f,
+"The results of {} have no {} column to sort or filter by.", name, column),
         }
     }
 }
 
-pub fn parse() -> Result<(String, String, Decimal), ArgsError>
+impl std::error::Error for ArgsError {}
+
+pub fn parse() -> Result<Cli, ArgsError>
 {
     let args: Vec<String> = env::args().collect();
+    parse_from(&args)
+}
 
-    if args.len() == 2 && args[1] == "--help"
-    {
-        return Err(ArgsError::HelpRequested);
+/// Parses the full command line, `args[0]` being the program name.
+pub fn parse_from(args: &[String]) -> Result<Cli, ArgsError>
+{
+    let mut options = GlobalOptions::default();
+    let mut help = false;
+    let mut targets = None;
+    let mut positionals = Vec::new();
+
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => help = true,
+            "-V" | "--version" => return Err(ArgsError::VersionRequested),
+            "--offline" => options.offline = Some(PathBuf::from(option_value(arg, args.next())?)),
+            "--provider" => options.providers = Some(parse_providers(option_value(arg, args.next())?)?),
+            "--divergence-threshold" => {
+                let threshold = option_value(arg, args.next())?;
+                options.divergence_threshold = Some(parse_threshold(threshold)?);
+            },
+            "-o" | "--output" => {
+                let format = option_value(arg, args.next())?;
+                options.output = OutputFormat::from_str(format).map_err(ArgsError::InvalidOutputFormat)?;
+            },
+            "--to" => targets = Some(option_value(arg, args.next())?),
+            "--sort" => {
+                let order = option_value(arg, args.next())?;
+                options.sort = Some(SortOrder::from_str(order).map_err(ArgsError::InvalidSortOrder)?);
+            },
+            "--filter" => {
+                let codes = option_value(arg, args.next())?;
+                options.filter = Some(codes.split(',').map(parse_currency_code).collect::<Result<_, _>>()?);
+            },
+            option if option.starts_with("--") => return Err(ArgsError::UnknownOption(option.to_string())),
+            _ => positionals.push(arg.as_str()),
+        }
     }
-    else if args.len() == 4
-    {
 
-        let source_currency_code = &args[1];
-        let target_currency_code = &args[2];
-        let decimal_value = match Decimal::from_str(&args[3])
-        {
-            Ok(val) => val,
-            Err(_) =>  return Err(ArgsError::ParseError),
-        };
+    let (name, arguments) = match positionals.split_first() {
+        Some((&"help", arguments)) => return Err(ArgsError::HelpRequested(match arguments.first() {
+            Some(name) => Some(command_spec(name).ok_or_else(|| ArgsError::UnknownCommand(name.to_string()))?.name),
+            None => None,
+        })),
+        Some((name, arguments)) => (*name, arguments),
+        None if help => return Err(ArgsError::HelpRequested(None)),
+        None => return Err(ArgsError::MissingCommand),
+    };
+    let command_spec = command_spec(name).ok_or_else(|| ArgsError::UnknownCommand(name.to_string()))?;
+    if help {
+        return Err(ArgsError::HelpRequested(Some(command_spec.name)));
+    }
 
-        return Ok((source_currency_code.to_string(), target_currency_code.to_string(), decimal_value))
+    let command = match targets {
+        Some(targets) => parse_convert_many(command_spec.name, arguments, targets)?,
+        None => parse_command(command_spec.name, arguments)?,
+    };
+    // Checked here, as the columns do not depend on the data, so that a bad column
+    // is a usage error rather than a failure after the rates are fetched.
+    let sort_key = options.sort.map(|order| order.key);
+    let filter_key = options.filter.as_ref().map(|_| SortKey::Code);
+    for key in sort_key.into_iter().chain(filter_key) {
+        if key.position(command.columns()).is_none() {
+            return Err(ArgsError::MissingColumn(command_spec.name, key.column()));
+        }
+    }
+    Ok(Cli { options, command })
+}
 
+fn parse_command(name: &'static str, arguments: &[&str]) -> Result<Command, ArgsError>
+{
+    match (name, arguments) {
+        ("convert", [source, target, amount]) => Ok(Command::Convert {
+            source: parse_currency_code(source)?,
+            target: parse_currency_code(target)?,
+            amount: parse_amount(amount)?,
+        }),
+        ("rates", [source]) => Ok(Command::Rates { source: parse_currency_code(source)? }),
+        ("codes", []) => Ok(Command::Codes),
+        ("quota", []) => Ok(Command::Quota),
+        ("enriched", [source, target]) => Ok(Command::Enriched {
+            source: parse_currency_code(source)?,
+            target: parse_currency_code(target)?,
+        }),
+        ("historical", [source, date, amount @ ..]) if amount.len() <= 1 => Ok(Command::Historical {
+            source: parse_currency_code(source)?,
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| ArgsError::InvalidDate(date.to_string()))?,
+            amount: amount.first().map(|amount| parse_amount(amount)).transpose()?,
+        }),
+        ("snapshot", [output, bases @ ..]) if !bases.is_empty() => Ok(Command::Snapshot {
+            output: PathBuf::from(output),
+            bases: bases.iter().map(|base| parse_currency_code(base)).collect::<Result<_, _>>()?,
+        }),
+        ("batch", [input]) => Ok(Command::Batch { input: PathBuf::from(input) }),
+        ("history", [base, target, at @ ..]) if at.len() <= 1 => Ok(Command::History {
+            base: parse_currency_code(base)?,
+            target: parse_currency_code(target)?,
+            at: at.first().map(|at| parse_date_time(at)).transpose()?,
+        }),
+        _ => Err(ArgsError::InvalidArguments(name)),
     }
-    else if args.len() == 2 && args[1] == "--codes" 
-    {
-        return Ok((String::new(), String::new(), Decimal::zero()));
+}
+
+/// `convert <amount> <source> --to <code>[,<code>...]|all`; no other command takes `--to`.
+fn parse_convert_many(name: &'static str, arguments: &[&str], targets: &str) -> Result<Command, ArgsError>
+{
+    match (name, arguments) {
+        ("convert", [amount, source]) => Ok(Command::ConvertMany {
+            amount: parse_amount(amount)?,
+            source: parse_currency_code(source)?,
+            targets: match targets {
+                all if all.eq_ignore_ascii_case("all") => None,
+                codes => Some(codes.split(',').map(parse_currency_code).collect::<Result<_, _>>()?),
+            },
+        }),
+        _ => Err(ArgsError::InvalidArguments(name)),
     }
-    else if args.len() == 2 
+}
+
+fn option_value<'a>(option: &str, value: Option<&'a String>) -> Result<&'a str, ArgsError>
+{
+    value.map(String::as_str).ok_or_else(|| ArgsError::MissingOptionValue(option.to_string()))
+}
+
+pub fn parse_providers(names: &str) -> Result<Vec<ProviderKind>, ArgsError>
+{
+    names.split(',')
+        .map(|name| ProviderKind::from_str(name).map_err(|_| ArgsError::InvalidProvider(name.to_string())))
+        .collect()
+}
+
+pub fn parse_threshold(threshold: &str) -> Result<Decimal, ArgsError>
+{
+    match Decimal::from_str(threshold) {
+        Ok(threshold) if !threshold.is_sign_negative() => Ok(threshold),
+        _ => Err(ArgsError::InvalidThreshold(threshold.to_string())),
+    }
+}
+
+fn parse_amount(amount: &str) -> Result<Decimal, ArgsError>
+{
+    Decimal::from_str(amount).map_err(|_| ArgsError::InvalidAmount(amount.to_string()))
+}
+
+/// Accepts RFC 3339 or a bare date, which stands for the last second of that day in UTC.
+fn parse_date_time(date_time: &str) -> Result<DateTime<Utc>, ArgsError>
+{
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(date_time) {
+        return Ok(date_time.with_timezone(&Utc));
+    }
+    match NaiveDate::parse_from_str(date_time, "%Y-%m-%d")
     {
-        let source_currency_code = &args[1];
-        return Ok((source_currency_code.to_string(), String::new(), Decimal::zero()))
+        Ok(date) => Ok(date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap()).and_utc()),
+        Err(_) => Err(ArgsError::InvalidDate(date_time.to_string())),
     }
+}
 
-    return Err(ArgsError::InvalidNumberOfArguments);
+fn parse_currency_code(code: &str) -> Result<CurrencyCode, ArgsError>
+{
+    CurrencyCode::from_str(code).map_err(|_| ArgsError::InvalidCurrencyCode(code.to_string()))
 }
 
- 
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::SortKey;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        std::iter::once(PROGRAM).chain(args.iter().copied()).map(String::from).collect()
+    }
+
+    fn command(arguments: &[&str]) -> Command {
+        parse_from(&args(arguments)).unwrap().command
+    }
+
+    fn code(code: &str) -> CurrencyCode {
+        CurrencyCode::from_str(code).unwrap()
+    }
+
+    #[test]
+    fn parses_every_command() {
+        assert_eq!(command(&["convert", "usd", "PLN", "100.50"]),
+            Command::Convert { source: code("USD"), target: code("PLN"), amount: Decimal::from_str("100.50").unwrap() });
+        assert_eq!(command(&["convert", "USD", "PLN", "0"]),
+            Command::Convert { source: code("USD"), target: code("PLN"), amount: Decimal::ZERO });
+        assert_eq!(command(&["convert", "100", "usd", "--to", "EUR,gbp"]),
+            Command::ConvertMany { amount: Decimal::from(100), source: code("USD"), targets: Some(vec![code("EUR"), code("GBP")]) });
+        assert_eq!(command(&["convert", "--to", "ALL", "100", "USD"]),
+            Command::ConvertMany { amount: Decimal::from(100), source: code("USD"), targets: None });
+        assert_eq!(command(&["codes"]), Command::Codes);
+        assert_eq!(command(&["quota"]), Command::Quota);
+        assert_eq!(command(&["rates", "USD"]), Command::Rates { source: code("USD") });
+        assert_eq!(command(&["enriched", "USD", "JPY"]), Command::Enriched { source: code("USD"), target: code("JPY") });
+        assert_eq!(command(&["historical", "USD", "2024-03-01"]),
+            Command::Historical { source: code("USD"), date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), amount: None });
+        assert_eq!(command(&["historical", "USD", "2024-03-01", "100"]),
+            Command::Historical { source: code("USD"), date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), amount: Some(Decimal::from(100)) });
+        assert_eq!(command(&["snapshot", "rates.json", "USD", "eur"]),
+            Command::Snapshot { output: PathBuf::from("rates.json"), bases: vec![code("USD"), code("EUR")] });
+        assert_eq!(command(&["batch", "-"]), Command::Batch { input: PathBuf::from("-") });
+        assert_eq!(command(&["history", "USD", "PLN"]), Command::History { base: code("USD"), target: code("PLN"), at: None });
+        assert_eq!(command(&["history", "USD", "PLN", "2024-03-22T12:00:00+01:00"]),
+            Command::History { base: code("USD"), target: code("PLN"), at: DateTime::from_timestamp(1711105200, 0) });
+        assert_eq!(command(&["history", "USD", "PLN", "2024-03-22"]),
+            Command::History { base: code("USD"), target: code("PLN"), at: DateTime::from_timestamp(1711151999, 0) });
+    }
+
+    #[test]
+    fn parses_global_options_anywhere() {
+        let cli = parse_from(&args(&["--offline", "rates.json", "convert", "USD", "--provider", "ecb,Frankfurter", "PLN", "10",
+            "--divergence-threshold", "0.005", "-o", "csv", "--sort", "rate:desc", "--filter", "eur,GBP"])).unwrap();
+
+        assert_eq!(cli.command, Command::Convert { source: code("USD"), target: code("PLN"), amount: Decimal::from(10) });
+        assert_eq!(cli.options, GlobalOptions {
+            offline: Some(PathBuf::from("rates.json")),
+            providers: Some(vec![ProviderKind::Ecb, ProviderKind::Frankfurter]),
+            divergence_threshold: Some(Decimal::from_str("0.005").unwrap()),
+            output: OutputFormat::Csv,
+            sort: Some(SortOrder { key: SortKey::Rate, descending: true }),
+            filter: Some(vec![code("EUR"), code("GBP")]),
+        });
+        assert_eq!(parse_from(&args(&["codes"])).unwrap().options, GlobalOptions::default());
+    }
+
+    #[test]
+    fn requests_general_and_command_help() {
+        assert!(matches!(parse_from(&args(&["--help"])), Err(ArgsError::HelpRequested(None))));
+        assert!(matches!(parse_from(&args(&["help"])), Err(ArgsError::HelpRequested(None))));
+        assert!(matches!(parse_from(&args(&["help", "convert"])), Err(ArgsError::HelpRequested(Some("convert")))));
+        assert!(matches!(parse_from(&args(&["rates", "-h"])), Err(ArgsError::HelpRequested(Some("rates")))));
+        assert!(matches!(parse_from(&args(&["--version"])), Err(ArgsError::VersionRequested)));
+
+        for command_spec in COMMANDS {
+            let help = ArgsError::HelpRequested(Some(command_spec.name)).to_string();
+            assert!(help.contains(command_spec.arguments), "{}", help);
+            assert!(ArgsError::HelpRequested(None).to_string().contains(command_spec.summary));
+        }
+        assert_eq!(ArgsError::HelpRequested(None).exit_code(), 0);
+    }
+
+    #[test]
+    fn reports_invalid_arguments() {
+        assert!(matches!(parse_from(&args(&[])), Err(ArgsError::MissingCommand)));
+        assert!(matches!(parse_from(&args(&["USD", "PLN", "10"])), Err(ArgsError::UnknownCommand(_))));
+        assert!(matches!(parse_from(&args(&["help", "fetch"])), Err(ArgsError::UnknownCommand(_))));
+        assert!(matches!(parse_from(&args(&["codes", "--all"])), Err(ArgsError::UnknownOption(_))));
+        assert!(matches!(parse_from(&args(&["codes", "--offline"])), Err(ArgsError::MissingOptionValue(_))));
+        assert!(matches!(parse_from(&args(&["convert", "USD", "PLN"])), Err(ArgsError::InvalidArguments("convert"))));
+        assert!(matches!(parse_from(&args(&["codes", "USD"])), Err(ArgsError::InvalidArguments("codes"))));
+        assert!(matches!(parse_from(&args(&["convert", "USD", "PLN", "10", "--to", "EUR"])), Err(ArgsError::InvalidArguments("convert"))));
+        assert!(matches!(parse_from(&args(&["rates", "USD", "--to", "EUR"])), Err(ArgsError::InvalidArguments("rates"))));
+        assert!(matches!(parse_from(&args(&["convert", "10", "USD", "--to", "EUR,"])), Err(ArgsError::InvalidCurrencyCode(_))));
+        assert!(matches!(parse_from(&args(&["snapshot", "rates.json"])), Err(ArgsError::InvalidArguments("snapshot"))));
+        assert!(matches!(parse_from(&args(&["convert", "USD", "PLN", "abc"])), Err(ArgsError::InvalidAmount(_))));
+        assert!(matches!(parse_from(&args(&["rates", "USDD"])), Err(ArgsError::InvalidCurrencyCode(_))));
+        assert!(matches!(parse_from(&args(&["historical", "USD", "01.03.2024"])), Err(ArgsError::InvalidDate(_))));
+        assert!(matches!(parse_from(&args(&["history", "USD", "PLN", "yesterday"])), Err(ArgsError::InvalidDate(_))));
+        assert!(matches!(parse_from(&args(&["codes", "--provider", "ecb,fixer"])), Err(ArgsError::InvalidProvider(_))));
+        assert!(matches!(parse_from(&args(&["codes", "--divergence-threshold", "-1"])), Err(ArgsError::InvalidThreshold(_))));
+        assert!(matches!(parse_from(&args(&["codes", "--output", "xml"])), Err(ArgsError::InvalidOutputFormat(_))));
+        assert!(matches!(parse_from(&args(&["rates", "USD", "--sort", "rate:up"])), Err(ArgsError::InvalidSortOrder(_))));
+        assert!(matches!(parse_from(&args(&["rates", "USD", "--filter", "EUR,XX"])), Err(ArgsError::InvalidCurrencyCode(_))));
+        assert!(matches!(parse_from(&args(&["convert", "USD", "PLN", "10", "--sort", "name"])), Err(ArgsError::MissingColumn("convert", "name"))));
+        assert!(matches!(parse_from(&args(&["quota", "--filter", "EUR"])), Err(ArgsError::MissingColumn("quota", "target"))));
+        assert!(parse_from(&args(&["codes", "--sort", "name:desc", "--filter", "EUR"])).is_ok());
+        assert_eq!(ArgsError::MissingCommand.exit_code(), EXIT_USAGE);
+    }
+
+    #[test]
+    fn maps_errors_to_distinct_exit_codes() {
+        assert_eq!(exit_code(&ExchangeRateError::QuotaReached), EXIT_QUOTA_REACHED);
+        assert_eq!(exit_code(&ExchangeRateError::RateLimited), EXIT_QUOTA_REACHED);
+        assert_eq!(exit_code(&ExchangeRateError::InvalidKey), EXIT_UNAUTHORIZED);
+        assert_eq!(exit_code(&ExchangeRateError::InactiveAccount), EXIT_UNAUTHORIZED);
+        assert_eq!(exit_code(&ExchangeRateError::Server(reqwest::StatusCode::BAD_GATEWAY)), EXIT_UNAVAILABLE);
+        assert_eq!(exit_code(&ExchangeRateError::UnsupportedCode), EXIT_INVALID_REQUEST);
+        assert_eq!(exit_code(&ExchangeRateError::InvalidInput("no header".to_string())), EXIT_INVALID_REQUEST);
+        assert_eq!(exit_code(&ExchangeRateError::InvalidResponse("no rates".to_string())), EXIT_FAILURE);
+
+        let help = ArgsError::HelpRequested(None).to_string();
+        for code in [EXIT_FAILURE, EXIT_USAGE, EXIT_QUOTA_REACHED, EXIT_UNAUTHORIZED, EXIT_UNAVAILABLE, EXIT_INVALID_REQUEST] {
+            assert!(help.contains(&format!("\n  {}  ", code)), "{}", help);
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::LatestRates;
+use crate::money::Money;
+use crate::rate_table::RateTable;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use csv::StringRecord;
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+
+/// The base currency and, for past rates, the date of a table of rates a batch needs.
+pub type RatesKey = (CurrencyCode, Option<NaiveDate>);
+
+/// An input record as read, so that every field can be written back unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputRecord
+{
+    /// Fields in the order of the CSV header.
+    Csv(StringRecord),
+    Json(Map<String, Value>),
+}
+
+impl Default for InputRecord
+{
+    fn default() -> Self
+    {
+        InputRecord::Csv(StringRecord::new())
+    }
+}
+
+/// One input row as written, so that invalid rows can be reported as they were given.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchRow
+{
+    /// Line of the input file the row starts on.
+    pub line: u64,
+    pub amount: String,
+    pub from: String,
+    pub to: String,
+    pub date: Option<String>,
+    pub record: InputRecord,
+}
+
+impl BatchRow
+{
+    /// The field at `index` of the input columns, named `column`. JSON strings are given
+    /// without quotes and other JSON values as written.
+    pub fn field(&self, index: usize, column: &str) -> Option<String>
+    {
+        match &self.record {
+            InputRecord::Csv(record) => record.get(index).map(str::to_string),
+            InputRecord::Json(object) => match object.get(column)? {
+                Value::Null => None,
+                Value::String(text) => Some(text.clone()),
+                value => Some(value.to_string()),
+            },
+        }
+    }
+}
+
+/// A row whose fields are all valid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ConversionRequest
+{
+    amount: Decimal,
+    from: CurrencyCode,
+    to: CurrencyCode,
+    date: Option<NaiveDate>,
+}
+
+impl TryFrom<&BatchRow> for ConversionRequest
+{
+    type Error = String;
+
+    fn try_from(row: &BatchRow) -> Result<Self, Self::Error>
+    {
+        let code = |code: &str| CurrencyCode::from_str(code).map_err(|_| format!("invalid currency code: {}", code));
+        Ok(ConversionRequest {
+            amount: Decimal::from_str(&row.amount).map_err(|_| format!("invalid amount: {}", row.amount))?,
+            from: code(&row.from)?,
+            to: code(&row.to)?,
+            date: match row.date.as_deref() {
+                Some(date) => Some(NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("invalid date: {}", date))?),
+                None => None,
+            },
+        })
+    }
+}
+
+/// A converted row: `amount` of `from` is worth `result` at `rate`, published at `last_update`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchConversion
+{
+    pub rate: Decimal,
+    pub result: Money,
+    pub last_update: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult
+{
+    pub row: BatchRow,
+    /// Why the row could not be converted; the other rows are converted regardless.
+    pub conversion: Result<BatchConversion, String>,
+}
+
+/// Conversions read from a CSV file with a header row or from JSON lines, each with
+/// an `amount`, a `from` and a `to` currency and optionally the `date` of the rates to use.
+#[derive(Debug, Clone)]
+pub struct Batch
+{
+    /// The CSV header, or every JSON field name in the order first seen.
+    columns: Vec<String>,
+    rows: Vec<(BatchRow, Result<ConversionRequest, String>)>,
+}
+
+impl Batch
+{
+    pub fn new(columns: Vec<String>, rows: Vec<BatchRow>) -> Self
+    {
+        Self { columns, rows: rows.into_iter().map(Self::validated).collect() }
+    }
+
+    /// Reads JSON lines when the first non-blank character opens an object, and CSV otherwise.
+    pub fn read(input: &str) -> Result<Self, ExchangeRateError>
+    {
+        match input.trim_start().starts_with('{') {
+            true => Ok(Self::from_json_lines(input)),
+            false => Self::from_csv(input),
+        }
+    }
+
+    /// Fails only if the header row lacks a column; malformed records become row errors.
+    pub fn from_csv(input: &str) -> Result<Self, ExchangeRateError>
+    {
+        let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).flexible(true).from_reader(input.as_bytes());
+        let headers = reader.headers().map_err(|e| ExchangeRateError::InvalidInput(e.to_string()))?.clone();
+        let column = |name: &str| headers.iter().position(|header| header.eq_ignore_ascii_case(name));
+        let (amount, from, to) = match (column("amount"), column("from"), column("to")) {
+            (Some(amount), Some(from), Some(to)) => (amount, from, to),
+            _ => return Err(ExchangeRateError::InvalidInput("the CSV header must name the amount, from and to columns".to_string())),
+        };
+        let date = column("date");
+
+        let mut batch = Self { columns: headers.iter().map(str::to_string).collect(), rows: Vec::new() };
+        for record in reader.records() {
+            match record {
+                Ok(record) => {
+                    let field = |index: usize| record.get(index).unwrap_or_default().to_string();
+                    batch.rows.push(Self::validated(BatchRow {
+                        line: record.position().map_or(0, |position| position.line()),
+                        amount: field(amount),
+                        from: field(from),
+                        to: field(to),
+                        date: date.map(field).filter(|date| !date.is_empty()),
+                        record: InputRecord::Csv(record.clone()),
+                    }));
+                },
+                Err(e) => {
+                    let line = e.position().map_or(0, |position| position.line());
+                    batch.rows.push((BatchRow { line, ..BatchRow::default() }, Err(e.to_string())));
+                },
+            }
+        }
+        Ok(batch)
+    }
+
+    /// Amounts may be written as JSON numbers, which are read exactly as written, or
+    /// strings; blank lines are skipped.
+    pub fn from_json_lines(input: &str) -> Self
+    {
+        let mut batch = Self { columns: Vec::new(), rows: Vec::new() };
+        for (index, line) in input.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let line_number = index as u64 + 1;
+            let object = match serde_json::from_str::<Value>(line) {
+                Ok(Value::Object(object)) => object,
+                Ok(_) => {
+                    batch.rows.push((BatchRow { line: line_number, ..BatchRow::default() }, Err(format!("line {}: not a JSON object", line_number))));
+                    continue;
+                },
+                Err(e) => {
+                    batch.rows.push((BatchRow { line: line_number, ..BatchRow::default() }, Err(format!("line {}: {}", line_number, e))));
+                    continue;
+                },
+            };
+            for name in object.keys() {
+                if !batch.columns.contains(name) {
+                    batch.columns.push(name.clone());
+                }
+            }
+            let field = |name: &str| match object.get(name) {
+                Some(Value::String(text)) => Some(text.clone()),
+                Some(Value::Number(number)) => Some(number.to_string()),
+                _ => None,
+            };
+            batch.rows.push(Self::validated(BatchRow {
+                line: line_number,
+                amount: field("amount").unwrap_or_default(),
+                from: field("from").unwrap_or_default(),
+                to: field("to").unwrap_or_default(),
+                date: field("date"),
+                record: InputRecord::Json(object.clone()),
+            }));
+        }
+        batch
+    }
+
+    fn validated(row: BatchRow) -> (BatchRow, Result<ConversionRequest, String>)
+    {
+        let request = ConversionRequest::try_from(&row);
+        (row, request)
+    }
+
+    /// The input's columns, written back in front of the conversions.
+    pub fn columns(&self) -> &[String]
+    {
+        &self.columns
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.rows.is_empty()
+    }
+
+    /// The tables of rates the valid rows need, each fetched once however many rows share it.
+    pub fn rates_keys(&self) -> Vec<RatesKey>
+    {
+        self.rows.iter()
+            .filter_map(|(_, request)| request.as_ref().ok())
+            .map(|request| (request.from, request.date))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Converts every valid row with the table fetched for its key; a failed fetch
+    /// is reported on each row that needed it.
+    pub fn convert(self, rates: &HashMap<RatesKey, Result<LatestRates, String>>) -> Vec<BatchResult>
+    {
+        let rate_tables: HashMap<&RatesKey, Result<(RateTable, Option<i64>), &String>> = rates.iter()
+            .map(|(key, latest_rates)| {
+                let rate_table = latest_rates.as_ref()
+                    .map(|latest_rates| (RateTable::from_latest_rates(latest_rates), latest_rates.time_last_update_unix));
+                (key, rate_table)
+            })
+            .collect();
+
+        self.rows.into_iter()
+            .map(|(row, request)| {
+                let conversion = request.and_then(|request| {
+                    let (rate_table, last_update) = match rate_tables.get(&(request.from, request.date)) {
+                        Some(Ok(rate_table)) => rate_table,
+                        Some(Err(e)) => return Err(e.to_string()),
+                        None => return Err(format!("no rates against {}", request.from)),
+                    };
+                    let rate = rate_table.rate(request.from, request.to).ok_or_else(|| format!("no rate for {} -> {}", request.from, request.to))?;
+                    let result = rate_table.convert(request.amount, request.from, request.to).ok_or("the converted amount is too large")?;
+                    Ok(BatchConversion {
+                        rate,
+                        result,
+                        last_update: last_update.and_then(|unix| DateTime::from_timestamp(unix, 0)),
+                    })
+                });
+                BatchResult { row, conversion }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{OutputFormat, Records};
+
+    fn pln() -> CurrencyCode {
+        CurrencyCode::from_str("PLN").unwrap()
+    }
+
+    fn usd_rates() -> LatestRates {
+        LatestRates {
+            base: CurrencyCode::USD,
+            conversion_rates: HashMap::from([
+                ("USD".to_string(), Decimal::ONE),
+                ("PLN".to_string(), Decimal::from(4)),
+            ]),
+            time_last_update_unix: Some(1711065601),
+            time_next_update_unix: None,
+        }
+    }
+
+    #[test]
+    fn reads_csv_with_columns_in_any_order() {
+        let batch = Batch::read("to,amount,from,date\nPLN, 10 ,usd,\n\"PLN\",5,USD,2024-03-01\n").unwrap();
+
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch.rows[0].0, BatchRow {
+            line: 2,
+            amount: "10".to_string(),
+            from: "usd".to_string(),
+            to: "PLN".to_string(),
+            date: None,
+            record: InputRecord::Csv(StringRecord::from(vec!["PLN", "10", "usd", ""])),
+        });
+        assert_eq!(batch.rates_keys(), [(CurrencyCode::USD, None), (CurrencyCode::USD, NaiveDate::from_ymd_opt(2024, 3, 1))]);
+
+        assert!(matches!(Batch::read("amount,currency\n10,USD\n"), Err(ExchangeRateError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn reads_json_lines() {
+        let batch = Batch::read("{\"amount\": 12345678901234567.89, \"from\": \"USD\", \"to\": \"PLN\"}\n\n{\"amount\": \"1\", \"from\": \"USD\", \"to\": \"EUR\", \"date\": \"2024-03-01\"}\n[1]\n").unwrap();
+
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.rows[0].1, Ok(ConversionRequest { amount: Decimal::from_str("12345678901234567.89").unwrap(), from: CurrencyCode::USD, to: pln(), date: None }));
+        assert_eq!(batch.rows[1].0.line, 3);
+        assert_eq!(batch.rows[2].1, Err("line 4: not a JSON object".to_string()));
+        assert_eq!(batch.columns(), ["amount", "from", "to", "date"]);
+    }
+
+    #[test]
+    fn writes_the_input_columns_back() {
+        let rates = HashMap::from([((CurrencyCode::USD, None), Ok(usd_rates()))]);
+
+        let batch = Batch::read("id,amount,from,to\ntx-1,10,USD,PLN\ntx-2,1,USD,JPY\n").unwrap();
+        let columns = batch.columns().to_vec();
+        let records = Records::batch(&columns, &batch.convert(&rates));
+        assert_eq!(records.render(OutputFormat::Csv), "id,amount,from,to,rate,result,last_update,error
+tx-1,10,USD,PLN,4,40.00,2024-03-22T00:00:01Z,
+tx-2,1,USD,JPY,,,,no rate for USD -> JPY
+");
+
+        let batch = Batch::read("{\"id\": 7, \"amount\": \"10\", \"from\": \"USD\", \"to\": \"PLN\"}\n{\"amount\": 1, \"from\": \"USD\", \"to\": \"PLN\", \"note\": \"refund\"}\n").unwrap();
+        let columns = batch.columns().to_vec();
+        let records = Records::batch(&columns, &batch.convert(&rates));
+        assert_eq!(records.render(OutputFormat::Csv), "id,amount,from,to,note,rate,result,last_update,error
+7,10,USD,PLN,,4,40.00,2024-03-22T00:00:01Z,
+,1,USD,PLN,refund,4,4.00,2024-03-22T00:00:01Z,
+");
+    }
+
+    #[test]
+    fn reports_row_errors_without_failing_the_batch() {
+        let batch = Batch::read("amount,from,to,date\n10,USD,PLN,\nten,USD,PLN,\n1,USD,JPY,\n1,USD,PLN,2024-03-01\n1,USD,PLN,01.03.2024\n79228162514264337593543950335,USD,PLN,\n").unwrap();
+        let rates = HashMap::from([
+            ((CurrencyCode::USD, None), Ok(usd_rates())),
+            ((CurrencyCode::USD, NaiveDate::from_ymd_opt(2024, 3, 1)), Err("Request quota reached.".to_string())),
+        ]);
+
+        let results = batch.convert(&rates);
+        let conversions: Vec<_> = results.iter().map(|result| result.conversion.clone()).collect();
+        assert_eq!(conversions, [
+            Ok(BatchConversion { rate: Decimal::from(4), result: Money::new(Decimal::from(40), pln()), last_update: DateTime::from_timestamp(1711065601, 0) }),
+            Err("invalid amount: ten".to_string()),
+            Err("no rate for USD -> JPY".to_string()),
+            Err("Request quota reached.".to_string()),
+            Err("invalid date: 01.03.2024".to_string()),
+            Err("the converted amount is too large".to_string()),
+        ]);
+        assert_eq!(results[4].row.line, 6);
+    }
+}
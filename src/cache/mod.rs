@@ -0,0 +1,196 @@
+use crate::file;
+use crate::models::ApiResponse;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Used for responses without `time_next_update_unix`, such as the supported codes list.
+pub const DEFAULT_TTL_SECONDS: i64 = 24 * 60 * 60;
+
+/// Identifies an endpoint and API key in cache keys without revealing the key, so that
+/// the responses of different endpoints or accounts sharing a cache are kept apart.
+pub fn endpoint_tag(prefix: &str, api_key: &str) -> String
+{
+    // FNV-1a, which unlike `DefaultHasher` gives the same tag in every build.
+    let hash = prefix.bytes().chain([0]).chain(api_key.bytes())
+        .fold(0xcbf29ce484222325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResponse
+{
+    expires_unix: i64,
+    api_response: ApiResponse,
+}
+
+/// Keeps successful responses in memory and, optionally, as JSON files in a directory,
+/// until the API's `time_next_update_unix` has passed.
+#[derive(Debug, Clone)]
+pub struct ResponseCache
+{
+    entries: Arc<Mutex<HashMap<String, CachedResponse>>>,
+    directory: Option<PathBuf>,
+    default_ttl_seconds: i64,
+}
+
+impl ResponseCache
+{
+    pub fn in_memory() -> Self
+    {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            directory: None,
+            default_ttl_seconds: DEFAULT_TTL_SECONDS,
+        }
+    }
+
+    /// Also stores entries in `directory`, so they survive between runs of the program.
+    pub fn on_disk(directory: PathBuf) -> Result<Self, std::io::Error>
+    {
+        fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory: Some(directory),
+            ..Self::in_memory()
+        })
+    }
+
+    pub fn with_default_ttl(mut self, default_ttl_seconds: i64) -> Self
+    {
+        self.default_ttl_seconds = default_ttl_seconds;
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<ApiResponse>
+    {
+        self.get_at(key, Utc::now().timestamp())
+    }
+
+    pub fn insert(&self, key: &str, api_response: &ApiResponse)
+    {
+        self.insert_at(key, api_response, Utc::now().timestamp())
+    }
+
+    pub fn clear(&self)
+    {
+        let mut entries = self.entries.lock().unwrap();
+        for key in entries.keys() {
+            if let Some(path) = self.path(key) {
+                let _ = fs::remove_file(path);
+            }
+        }
+        entries.clear();
+    }
+
+    fn get_at(&self, key: &str, now: i64) -> Option<ApiResponse>
+    {
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.contains_key(key) {
+            let cached_response = self.load(key)?;
+            entries.insert(key.to_string(), cached_response);
+        }
+
+        let cached_response = &entries[key];
+        if cached_response.expires_unix > now {
+            Some(cached_response.api_response.clone())
+        }
+        else {
+            entries.remove(key);
+            if let Some(path) = self.path(key) {
+                let _ = fs::remove_file(path);
+            }
+            None
+        }
+    }
+
+    fn insert_at(&self, key: &str, api_response: &ApiResponse, now: i64)
+    {
+        let cached_response = CachedResponse {
+            expires_unix: api_response.time_next_update_unix.unwrap_or(now + self.default_ttl_seconds),
+            api_response: api_response.clone(),
+        };
+        self.store(key, &cached_response);
+        self.entries.lock().unwrap().insert(key.to_string(), cached_response);
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf>
+    {
+        self.directory.as_ref().map(|directory| directory.join(format!("{}.json", key)))
+    }
+
+    fn load(&self, key: &str) -> Option<CachedResponse>
+    {
+        let json = fs::read_to_string(self.path(key)?).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    fn store(&self, key: &str, cached_response: &CachedResponse)
+    {
+        // The cache is an optimisation, a file that cannot be written only costs an extra request later.
+        if let (Some(path), Ok(json)) = (self.path(key), serde_json::to_string(cached_response)) {
+            let _ = file::write_atomically(&path, &json);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_response(time_next_update_unix: Option<i64>) -> ApiResponse {
+        serde_json::from_value(serde_json::json!({
+            "result": "success",
+            "time_next_update_unix": time_next_update_unix,
+            "base_code": "USD",
+            "conversion_rates": { "USD": 1, "PLN": 3.9887 }
+        })).unwrap()
+    }
+
+    #[test]
+    fn endpoint_tags_differ_by_prefix_and_key() {
+        let keyed = endpoint_tag("https://v6.exchangerate-api.com", "111111111111111111111111");
+
+        assert_eq!(keyed, endpoint_tag("https://v6.exchangerate-api.com", "111111111111111111111111"));
+        assert_ne!(keyed, endpoint_tag("https://v6.exchangerate-api.com", "222222222222222222222222"));
+        assert_ne!(endpoint_tag("https://v6.exchangerate-api.com", ""), endpoint_tag("https://open.er-api.com", ""));
+        assert!(!keyed.contains("1111"));
+    }
+
+    #[test]
+    fn entry_expires_at_time_next_update() {
+        let response_cache = ResponseCache::in_memory();
+        response_cache.insert_at("latest_USD", &api_response(Some(2000)), 1000);
+
+        assert!(response_cache.get_at("latest_USD", 1999).is_some());
+        assert!(response_cache.get_at("latest_USD", 2000).is_none());
+        assert!(response_cache.get_at("latest_USD", 1000).is_none());
+    }
+
+    #[test]
+    fn entry_without_update_time_uses_default_ttl() {
+        let response_cache = ResponseCache::in_memory().with_default_ttl(60);
+        response_cache.insert_at("codes", &api_response(None), 1000);
+
+        assert!(response_cache.get_at("codes", 1059).is_some());
+        assert!(response_cache.get_at("codes", 1060).is_none());
+    }
+
+    #[test]
+    fn disk_entries_are_shared_between_instances() {
+        let directory = std::env::temp_dir().join(format!("exchange_rate_api_cache_{}", std::process::id()));
+
+        let response_cache = ResponseCache::on_disk(directory.clone()).unwrap();
+        response_cache.insert_at("latest_USD", &api_response(Some(2000)), 1000);
+
+        let response_cache = ResponseCache::on_disk(directory.clone()).unwrap();
+        let cached = response_cache.get_at("latest_USD", 1500).unwrap();
+        assert_eq!(cached.base_code.as_deref(), Some("USD"));
+        assert!(!file::tmp_path(&directory.join("latest_USD.json")).unwrap().exists());
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
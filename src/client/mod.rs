@@ -0,0 +1,733 @@
+use crate::cache::ResponseCache;
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::request_handler::{ApiRequest, ConversionRates, EnrichedRequest, HistoricalRates, QuotaRequest, StandardRequest, SupportedCodes};
+use crate::rate_limit::RateLimiter;
+use crate::rate_table::RateTable;
+use crate::retry::RetryPolicy;
+
+use chrono::NaiveDate;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+pub const DEFAULT_PREFIX: &str = "https://v6.exchangerate-api.com";
+/// Keyless endpoint; terms of use require attribution and allow fewer requests.
+pub const OPEN_ACCESS_PREFIX: &str = "https://open.er-api.com";
+pub const OPEN_ACCESS_ATTRIBUTION: &str = "Rates By Exchange Rate API (https://www.exchangerate-api.com)";
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Owns one configured `reqwest::Client` together with the API key and URL prefix,
+/// so every request built from it shares the same connection pool.
+#[derive(Clone)]
+pub struct ExchangeRateClient
+{
+    http_client: Client,
+    api_key: String,
+    prefix: String,
+    settings: RequestSettings,
+}
+
+impl ExchangeRateClient
+{
+    pub fn new(api_key: &str) -> Result<Self, ExchangeRateError>
+    {
+        Self::builder(api_key).build()
+    }
+
+    pub fn builder(api_key: &str) -> ExchangeRateClientBuilder
+    {
+        ExchangeRateClientBuilder::new(api_key)
+    }
+
+    pub fn api_key(&self) -> &str
+    {
+        &self.api_key
+    }
+
+    pub fn prefix(&self) -> &str
+    {
+        &self.prefix
+    }
+
+    pub fn http_client(&self) -> &Client
+    {
+        &self.http_client
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy
+    {
+        &self.settings.retry_policy
+    }
+
+    /// Requests left in the current plan period as tracked locally, if a rate limiter with a quota counter is configured.
+    pub fn remaining_quota(&self) -> Option<u64>
+    {
+        self.settings.rate_limiter.as_ref().and_then(RateLimiter::remaining_quota)
+    }
+
+    pub fn standard_request(&self, source: &CurrencyCode, target: &CurrencyCode, decimal_value: &Decimal) -> StandardRequest
+    {
+        self.settings.apply(StandardRequest::with_client(self.http_client.clone(), source, target, decimal_value, &self.api_key, &self.prefix))
+    }
+
+    pub fn enriched_request(&self, source: &CurrencyCode, target: &CurrencyCode) -> EnrichedRequest
+    {
+        self.settings.apply(EnrichedRequest::with_client(self.http_client.clone(), source, target, &self.api_key, &self.prefix))
+    }
+
+    pub fn supported_codes(&self) -> SupportedCodes
+    {
+        self.settings.apply(SupportedCodes::with_client(self.http_client.clone(), &self.api_key, &self.prefix))
+    }
+
+    pub fn conversion_rates(&self, source: &CurrencyCode) -> ConversionRates
+    {
+        self.settings.apply(ConversionRates::with_client(self.http_client.clone(), source, &self.api_key, &self.prefix))
+    }
+
+    pub fn historical_rates(&self, source: &CurrencyCode, date: &NaiveDate, amount: Option<&Decimal>) -> HistoricalRates
+    {
+        self.settings.apply(HistoricalRates::with_client(self.http_client.clone(), source, date, amount, &self.api_key, &self.prefix))
+    }
+
+    /// Not rate limited, so that the quota can be checked once the local one is used up.
+    pub fn quota_request(&self) -> QuotaRequest
+    {
+        QuotaRequest::with_client(self.http_client.clone(), &self.api_key, &self.prefix)
+            .with_retry_policy(self.settings.retry_policy.clone())
+    }
+
+    /// Fetches the latest rates for `source` once, for any number of local conversions.
+    pub async fn rate_table(&self, source: &CurrencyCode) -> Result<Option<RateTable>, ExchangeRateError>
+    {
+        Ok(self.conversion_rates(source).fetch().await?
+            .map(|latest_rates| RateTable::from_latest_rates(&latest_rates)))
+    }
+}
+
+/// Client for the keyless open access endpoint, which only serves the latest rates.
+/// Users of the data must show `OPEN_ACCESS_ATTRIBUTION`.
+#[derive(Clone)]
+pub struct OpenAccessClient
+{
+    http_client: Client,
+    prefix: String,
+    settings: RequestSettings,
+}
+
+impl OpenAccessClient
+{
+    pub fn new() -> Result<Self, ExchangeRateError>
+    {
+        Self::builder().build()
+    }
+
+    pub fn builder() -> OpenAccessClientBuilder
+    {
+        OpenAccessClientBuilder { builder: ExchangeRateClientBuilder::new("") }
+    }
+
+    pub fn conversion_rates(&self, source: &CurrencyCode) -> ConversionRates
+    {
+        self.settings.apply(ConversionRates::open_access(self.http_client.clone(), source, &self.prefix))
+    }
+
+    /// Fetches the latest rates for `source` once, for any number of local conversions.
+    pub async fn rate_table(&self, source: &CurrencyCode) -> Result<Option<RateTable>, ExchangeRateError>
+    {
+        Ok(self.conversion_rates(source).fetch().await?
+            .map(|latest_rates| RateTable::from_latest_rates(&latest_rates)))
+    }
+}
+
+/// The retry policy, rate limiter and cache a client attaches to every request it builds.
+#[derive(Clone)]
+struct RequestSettings
+{
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+}
+
+impl RequestSettings
+{
+    fn apply<R: ConfigurableRequest>(&self, request: R) -> R
+    {
+        let mut request = request.with_retry_policy(self.retry_policy.clone());
+        if let Some(rate_limiter) = &self.rate_limiter {
+            request = request.with_rate_limiter(rate_limiter.clone());
+        }
+        if let Some(cache) = &self.cache {
+            request = request.with_cache(cache.clone());
+        }
+        request
+    }
+}
+
+/// The builder methods `RequestSettings::apply` calls, forwarded to each request's own.
+trait ConfigurableRequest: Sized
+{
+    fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self;
+    fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self;
+
+    /// Requests whose responses are never cached ignore the cache.
+    fn with_cache(self, _cache: ResponseCache) -> Self
+    {
+        self
+    }
+}
+
+impl ConfigurableRequest for StandardRequest
+{
+    fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self
+    {
+        StandardRequest::with_retry_policy(self, retry_policy)
+    }
+
+    fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self
+    {
+        StandardRequest::with_rate_limiter(self, rate_limiter)
+    }
+}
+
+impl ConfigurableRequest for EnrichedRequest
+{
+    fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self
+    {
+        EnrichedRequest::with_retry_policy(self, retry_policy)
+    }
+
+    fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self
+    {
+        EnrichedRequest::with_rate_limiter(self, rate_limiter)
+    }
+}
+
+impl ConfigurableRequest for SupportedCodes
+{
+    fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self
+    {
+        SupportedCodes::with_retry_policy(self, retry_policy)
+    }
+
+    fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self
+    {
+        SupportedCodes::with_rate_limiter(self, rate_limiter)
+    }
+
+    fn with_cache(self, cache: ResponseCache) -> Self
+    {
+        SupportedCodes::with_cache(self, cache)
+    }
+}
+
+impl ConfigurableRequest for ConversionRates
+{
+    fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self
+    {
+        ConversionRates::with_retry_policy(self, retry_policy)
+    }
+
+    fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self
+    {
+        ConversionRates::with_rate_limiter(self, rate_limiter)
+    }
+
+    fn with_cache(self, cache: ResponseCache) -> Self
+    {
+        ConversionRates::with_cache(self, cache)
+    }
+}
+
+impl ConfigurableRequest for HistoricalRates
+{
+    fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self
+    {
+        HistoricalRates::with_retry_policy(self, retry_policy)
+    }
+
+    fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self
+    {
+        HistoricalRates::with_rate_limiter(self, rate_limiter)
+    }
+
+    fn with_cache(self, cache: ResponseCache) -> Self
+    {
+        HistoricalRates::with_cache(self, cache)
+    }
+}
+
+pub struct ExchangeRateClientBuilder
+{
+    api_key: String,
+    prefix: Option<String>,
+    connect_timeout: Duration,
+    timeout: Duration,
+    user_agent: Option<String>,
+    proxy: Option<reqwest::Proxy>,
+    http_client: Option<Client>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+}
+
+impl ExchangeRateClientBuilder
+{
+    pub fn new(api_key: &str) -> Self
+    {
+        Self {
+            api_key: api_key.to_string(),
+            prefix: None,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            timeout: DEFAULT_TIMEOUT,
+            user_agent: None,
+            proxy: None,
+            http_client: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+            cache: None,
+        }
+    }
+
+    /// Base URL of the API, e.g. the address of a mock server in tests.
+    /// Defaults to `DEFAULT_PREFIX`.
+    pub fn prefix(mut self, prefix: &str) -> Self
+    {
+        self.prefix = Some(prefix.trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self
+    {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Total time allowed for a single request, including reading the body.
+    pub fn timeout(mut self, timeout: Duration) -> Self
+    {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self
+    {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self
+    {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Uses an already configured `reqwest::Client` (e.g. with custom TLS settings)
+    /// instead of building one; timeouts, user agent and proxy are then ignored.
+    pub fn http_client(mut self, http_client: Client) -> Self
+    {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self
+    {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self
+    {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Serves `supported_codes` and `conversion_rates` from the cache until the next rates update.
+    pub fn cache(mut self, cache: ResponseCache) -> Self
+    {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Fails with `InvalidKey` without an API key; `OpenAccessClient::builder` builds
+    /// clients for the keyless endpoint.
+    pub fn build(mut self) -> Result<ExchangeRateClient, ExchangeRateError>
+    {
+        if self.api_key.is_empty() {
+            return Err(ExchangeRateError::InvalidKey);
+        }
+        let http_client = self.build_http_client()?;
+        let api_key = std::mem::take(&mut self.api_key);
+        let prefix = self.prefix.take().unwrap_or_else(|| DEFAULT_PREFIX.to_string());
+        Ok(ExchangeRateClient { http_client, api_key, prefix, settings: self.settings() })
+    }
+
+    fn settings(self) -> RequestSettings
+    {
+        RequestSettings {
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            cache: self.cache,
+        }
+    }
+
+    fn build_http_client(&mut self) -> Result<Client, ExchangeRateError>
+    {
+        if let Some(http_client) = self.http_client.take() {
+            return Ok(http_client);
+        }
+
+        let mut builder = Client::builder()
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.timeout);
+        if let Some(user_agent) = self.user_agent.take() {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(proxy) = self.proxy.take() {
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Builds an `OpenAccessClient`, with the same settings as `ExchangeRateClientBuilder`
+/// except for the API key.
+pub struct OpenAccessClientBuilder
+{
+    builder: ExchangeRateClientBuilder,
+}
+
+impl OpenAccessClientBuilder
+{
+    /// Base URL of the keyless endpoint, e.g. the address of a mock server in tests.
+    /// Defaults to `OPEN_ACCESS_PREFIX`.
+    pub fn prefix(self, prefix: &str) -> Self
+    {
+        Self { builder: self.builder.prefix(prefix) }
+    }
+
+    pub fn connect_timeout(self, connect_timeout: Duration) -> Self
+    {
+        Self { builder: self.builder.connect_timeout(connect_timeout) }
+    }
+
+    /// Total time allowed for a single request, including reading the body.
+    pub fn timeout(self, timeout: Duration) -> Self
+    {
+        Self { builder: self.builder.timeout(timeout) }
+    }
+
+    pub fn user_agent(self, user_agent: &str) -> Self
+    {
+        Self { builder: self.builder.user_agent(user_agent) }
+    }
+
+    pub fn proxy(self, proxy: reqwest::Proxy) -> Self
+    {
+        Self { builder: self.builder.proxy(proxy) }
+    }
+
+    /// Uses an already configured `reqwest::Client` instead of building one;
+    /// timeouts, user agent and proxy are then ignored.
+    pub fn http_client(self, http_client: Client) -> Self
+    {
+        Self { builder: self.builder.http_client(http_client) }
+    }
+
+    pub fn retry_policy(self, retry_policy: RetryPolicy) -> Self
+    {
+        Self { builder: self.builder.retry_policy(retry_policy) }
+    }
+
+    pub fn rate_limiter(self, rate_limiter: RateLimiter) -> Self
+    {
+        Self { builder: self.builder.rate_limiter(rate_limiter) }
+    }
+
+    /// Serves `conversion_rates` from the cache until the next rates update.
+    pub fn cache(self, cache: ResponseCache) -> Self
+    {
+        Self { builder: self.builder.cache(cache) }
+    }
+
+    pub fn build(self) -> Result<OpenAccessClient, ExchangeRateError>
+    {
+        let mut builder = self.builder;
+        let http_client = builder.build_http_client()?;
+        let prefix = builder.prefix.take().unwrap_or_else(|| OPEN_ACCESS_PREFIX.to_string());
+        Ok(OpenAccessClient { http_client, prefix, settings: builder.settings() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::rate_limit::{LimitMode, QuotaCounter};
+    use wiremock::{MockServer, Mock, ResponseTemplate};
+    use wiremock::matchers::{header, method, path};
+
+    #[tokio::test]
+    async fn requests_share_configured_client() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "supported_codes": [["USD", "United States Dollar"], ["PLN", "Polish Zloty"]]
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/codes"))
+            .and(header("user-agent", "exchange-rate-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let client = ExchangeRateClient::builder("111111111111111111111111")
+            .prefix(&mock_server.uri())
+            .user_agent("exchange-rate-test")
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            match client.supported_codes().fetch().await {
+                Ok(Some(supported_codes)) => assert_eq!(supported_codes.len(), 2),
+                Ok(_) => panic!("Expected supported codes"),
+                Err(e) => panic!("Error fetching supported codes: {}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn keyed_client_needs_a_key() {
+        assert!(matches!(ExchangeRateClient::builder("").build(), Err(ExchangeRateError::InvalidKey)));
+        assert!(ExchangeRateClient::builder("111111111111111111111111").build().is_ok());
+        assert_eq!(OpenAccessClient::builder().build().unwrap().prefix, OPEN_ACCESS_PREFIX);
+    }
+
+    #[tokio::test]
+    async fn timeout_is_reported_as_transport_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/codes"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&mock_server)
+            .await;
+
+        let client = ExchangeRateClient::builder("111111111111111111111111")
+            .prefix(&mock_server.uri())
+            .timeout(Duration::from_millis(50))
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+
+        match client.supported_codes().fetch().await {
+            Err(ExchangeRateError::Transport(e)) => assert!(e.is_timeout()),
+            Err(e) => panic!("Expected a transport error, got: {}", e),
+            Ok(_) => panic!("The test failed: a timeout was expected, but success was received."),
+        }
+    }
+
+    #[tokio::test]
+    async fn local_quota_stops_requests_before_upstream() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "supported_codes": [["USD", "United States Dollar"]]
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/codes"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let rate_limiter = RateLimiter::new(LimitMode::Refuse)
+            .with_quota_counter(QuotaCounter::new(1, 1));
+        let client = ExchangeRateClient::builder("111111111111111111111111")
+            .prefix(&mock_server.uri())
+            .rate_limiter(rate_limiter)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.remaining_quota(), Some(1));
+        assert!(client.supported_codes().fetch().await.is_ok());
+        assert_eq!(client.remaining_quota(), Some(0));
+        assert!(matches!(client.supported_codes().fetch().await, Err(ExchangeRateError::QuotaReached)));
+    }
+
+    #[tokio::test]
+    async fn quota_status_is_fetched_when_local_quota_is_used_up() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "plan_quota": 1500,
+            "requests_remaining": 0,
+            "refresh_day_of_month": 17
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/quota"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let rate_limiter = RateLimiter::new(LimitMode::Refuse)
+            .with_quota_counter(QuotaCounter::new(0, 1));
+        let client = ExchangeRateClient::builder("111111111111111111111111")
+            .prefix(&mock_server.uri())
+            .rate_limiter(rate_limiter)
+            .build()
+            .unwrap();
+
+        let quota_status = client.quota_request().fetch().await.unwrap().unwrap();
+        assert_eq!(quota_status.requests_remaining, 0);
+        assert_eq!(client.remaining_quota(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn cached_rates_are_fetched_once_per_update_window() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = format!(r#"
+        {{
+            "result": "success",
+            "time_last_update_unix": {},
+            "time_next_update_unix": {},
+            "base_code": "USD",
+            "conversion_rates": {{ "USD": 1, "PLN": 3.9887 }}
+        }}"#, chrono::Utc::now().timestamp() - 60, chrono::Utc::now().timestamp() + 3600);
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/latest/USD"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ExchangeRateClient::builder("111111111111111111111111")
+            .prefix(&mock_server.uri())
+            .cache(ResponseCache::in_memory())
+            .build()
+            .unwrap();
+
+        let source = CurrencyCode::from_str("USD").unwrap();
+        for _ in 0..3 {
+            match client.conversion_rates(&source).fetch().await {
+                Ok(Some(latest_rates)) => assert_eq!(latest_rates.conversion_rates.len(), 2),
+                Ok(_) => panic!("Expected conversion rates"),
+                Err(e) => panic!("Error fetching conversion rates: {}", e),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn keyed_and_open_access_clients_do_not_share_cache_entries() {
+        let mock_server = MockServer::start().await;
+        let body = |pln: &str| format!(r#"
+        {{
+            "result": "success",
+            "time_next_update_unix": {},
+            "base_code": "USD",
+            "conversion_rates": {{ "USD": 1, "PLN": {} }}
+        }}"#, chrono::Utc::now().timestamp() + 3600, pln);
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/latest/USD"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body("3.9887")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v6/latest/USD"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body("4.0012")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let cache = ResponseCache::in_memory();
+        let keyed_client = ExchangeRateClient::builder("111111111111111111111111")
+            .prefix(&mock_server.uri())
+            .cache(cache.clone())
+            .build()
+            .unwrap();
+        let open_access_client = OpenAccessClient::builder()
+            .prefix(&mock_server.uri())
+            .cache(cache)
+            .build()
+            .unwrap();
+
+        let source = CurrencyCode::from_str("USD").unwrap();
+        for _ in 0..2 {
+            let keyed_rates = keyed_client.conversion_rates(&source).fetch().await.unwrap().unwrap();
+            let open_access_rates = open_access_client.conversion_rates(&source).fetch().await.unwrap().unwrap();
+            assert_eq!(keyed_rates.conversion_rates["PLN"].to_string(), "3.9887");
+            assert_eq!(open_access_rates.conversion_rates["PLN"].to_string(), "4.0012");
+        }
+    }
+
+    #[tokio::test]
+    async fn open_access_client_fetches_latest_rates_without_key() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "provider": "https://www.exchangerate-api.com",
+            "documentation": "https://www.exchangerate-api.com/docs/free",
+            "terms_of_use": "https://www.exchangerate-api.com/terms",
+            "time_last_update_unix": 1711238401,
+            "time_next_update_unix": 1711326391,
+            "time_eol_unix": 0,
+            "base_code": "USD",
+            "rates": { "USD": 1, "PLN": 3.9887 }
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/latest/USD"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let open_access_client = OpenAccessClient::builder()
+            .prefix(&mock_server.uri())
+            .build()
+            .unwrap();
+
+        let source = CurrencyCode::from_str("USD").unwrap();
+        let rate_table = open_access_client.rate_table(&source).await.unwrap().unwrap();
+        let target = CurrencyCode::from_str("PLN").unwrap();
+        assert_eq!(rate_table.convert(Decimal::from(10), source, target).unwrap().amount, Decimal::from_str("39.887").unwrap());
+    }
+
+    #[tokio::test]
+    async fn open_access_error_result_is_reported() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "error",
+            "error-type": "unsupported-code"
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/latest/VED"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .mount(&mock_server)
+            .await;
+
+        let open_access_client = OpenAccessClient::builder()
+            .prefix(&mock_server.uri())
+            .build()
+            .unwrap();
+
+        let source = CurrencyCode::from_str("VED").unwrap();
+        assert!(matches!(open_access_client.conversion_rates(&source).fetch().await, Err(ExchangeRateError::UnsupportedCode)));
+    }
+}
@@ -0,0 +1,188 @@
+use crate::currency::CurrencyInfo;
+
+/// Active ISO 4217 currencies, plus the local currencies ExchangeRate-API quotes without an ISO code
+/// (FOK, GGP, IMP, JEP, KID, TVD). Sorted by code for binary search.
+pub const CURRENCIES: &[CurrencyInfo] = &[
+    CurrencyInfo { code: "AED", numeric: Some(784), minor_units: Some(2), name: "UAE Dirham" },
+    CurrencyInfo { code: "AFN", numeric: Some(971), minor_units: Some(2), name: "Afghani" },
+    CurrencyInfo { code: "ALL", numeric: Some(8), minor_units: Some(2), name: "Lek" },
+    CurrencyInfo { code: "AMD", numeric: Some(51), minor_units: Some(2), name: "Armenian Dram" },
+    CurrencyInfo { code: "ANG", numeric: Some(532), minor_units: Some(2), name: "Netherlands Antillean Guilder" },
+    CurrencyInfo { code: "AOA", numeric: Some(973), minor_units: Some(2), name: "Kwanza" },
+    CurrencyInfo { code: "ARS", numeric: Some(32), minor_units: Some(2), name: "Argentine Peso" },
+    CurrencyInfo { code: "AUD", numeric: Some(36), minor_units: Some(2), name: "Australian Dollar" },
+    CurrencyInfo { code: "AWG", numeric: Some(533), minor_units: Some(2), name: "Aruban Florin" },
+    CurrencyInfo { code: "AZN", numeric: Some(944), minor_units: Some(2), name: "Azerbaijan Manat" },
+    CurrencyInfo { code: "BAM", numeric: Some(977), minor_units: Some(2), name: "Convertible Mark" },
+    CurrencyInfo { code: "BBD", numeric: Some(52), minor_units: Some(2), name: "Barbados Dollar" },
+    CurrencyInfo { code: "BDT", numeric: Some(50), minor_units: Some(2), name: "Taka" },
+    CurrencyInfo { code: "BGN", numeric: Some(975), minor_units: Some(2), name: "Bulgarian Lev" },
+    CurrencyInfo { code: "BHD", numeric: Some(48), minor_units: Some(3), name: "Bahraini Dinar" },
+    CurrencyInfo { code: "BIF", numeric: Some(108), minor_units: Some(0), name: "Burundi Franc" },
+    CurrencyInfo { code: "BMD", numeric: Some(60), minor_units: Some(2), name: "Bermudian Dollar" },
+    CurrencyInfo { code: "BND", numeric: Some(96), minor_units: Some(2), name: "Brunei Dollar" },
+    CurrencyInfo { code: "BOB", numeric: Some(68), minor_units: Some(2), name: "Boliviano" },
+    CurrencyInfo { code: "BOV", numeric: Some(984), minor_units: Some(2), name: "Mvdol" },
+    CurrencyInfo { code: "BRL", numeric: Some(986), minor_units: Some(2), name: "Brazilian Real" },
+    CurrencyInfo { code: "BSD", numeric: Some(44), minor_units: Some(2), name: "Bahamian Dollar" },
+    CurrencyInfo { code: "BTN", numeric: Some(64), minor_units: Some(2), name: "Ngultrum" },
+    CurrencyInfo { code: "BWP", numeric: Some(72), minor_units: Some(2), name: "Pula" },
+    CurrencyInfo { code: "BYN", numeric: Some(933), minor_units: Some(2), name: "Belarusian Ruble" },
+    CurrencyInfo { code: "BZD", numeric: Some(84), minor_units: Some(2), name: "Belize Dollar" },
+    CurrencyInfo { code: "CAD", numeric: Some(124), minor_units: Some(2), name: "Canadian Dollar" },
+    CurrencyInfo { code: "CDF", numeric: Some(976), minor_units: Some(2), name: "Congolese Franc" },
+    CurrencyInfo { code: "CHE", numeric: Some(947), minor_units: Some(2), name: "WIR Euro" },
+    CurrencyInfo { code: "CHF", numeric: Some(756), minor_units: Some(2), name: "Swiss Franc" },
+    CurrencyInfo { code: "CHW", numeric: Some(948), minor_units: Some(2), name: "WIR Franc" },
+    CurrencyInfo { code: "CLF", numeric: Some(990), minor_units: Some(4), name: "Unidad de Fomento" },
+    CurrencyInfo { code: "CLP", numeric: Some(152), minor_units: Some(0), name: "Chilean Peso" },
+    CurrencyInfo { code: "CNY", numeric: Some(156), minor_units: Some(2), name: "Yuan Renminbi" },
+    CurrencyInfo { code: "COP", numeric: Some(170), minor_units: Some(2), name: "Colombian Peso" },
+    CurrencyInfo { code: "COU", numeric: Some(970), minor_units: Some(2), name: "Unidad de Valor Real" },
+    CurrencyInfo { code: "CRC", numeric: Some(188), minor_units: Some(2), name: "Costa Rican Colon" },
+    CurrencyInfo { code: "CUC", numeric: Some(931), minor_units: Some(2), name: "Peso Convertible" },
+    CurrencyInfo { code: "CUP", numeric: Some(192), minor_units: Some(2), name: "Cuban Peso" },
+    CurrencyInfo { code: "CVE", numeric: Some(132), minor_units: Some(2), name: "Cabo Verde Escudo" },
+    CurrencyInfo { code: "CZK", numeric: Some(203), minor_units: Some(2), name: "Czech Koruna" },
+    CurrencyInfo { code: "DJF", numeric: Some(262), minor_units: Some(0), name: "Djibouti Franc" },
+    CurrencyInfo { code: "DKK", numeric: Some(208), minor_units: Some(2), name: "Danish Krone" },
+    CurrencyInfo { code: "DOP", numeric: Some(214), minor_units: Some(2), name: "Dominican Peso" },
+    CurrencyInfo { code: "DZD", numeric: Some(12), minor_units: Some(2), name: "Algerian Dinar" },
+    CurrencyInfo { code: "EGP", numeric: Some(818), minor_units: Some(2), name: "Egyptian Pound" },
+    CurrencyInfo { code: "ERN", numeric: Some(232), minor_units: Some(2), name: "Nakfa" },
+    CurrencyInfo { code: "ETB", numeric: Some(230), minor_units: Some(2), name: "Ethiopian Birr" },
+    CurrencyInfo { code: "EUR", numeric: Some(978), minor_units: Some(2), name: "Euro" },
+    CurrencyInfo { code: "FJD", numeric: Some(242), minor_units: Some(2), name: "Fiji Dollar" },
+    CurrencyInfo { code: "FKP", numeric: Some(238), minor_units: Some(2), name: "Falkland Islands Pound" },
+    CurrencyInfo { code: "FOK", numeric: None, minor_units: Some(2), name: "Faroese Krona" },
+    CurrencyInfo { code: "GBP", numeric: Some(826), minor_units: Some(2), name: "Pound Sterling" },
+    CurrencyInfo { code: "GEL", numeric: Some(981), minor_units: Some(2), name: "Lari" },
+    CurrencyInfo { code: "GGP", numeric: None, minor_units: Some(2), name: "Guernsey Pound" },
+    CurrencyInfo { code: "GHS", numeric: Some(936), minor_units: Some(2), name: "Ghana Cedi" },
+    CurrencyInfo { code: "GIP", numeric: Some(292), minor_units: Some(2), name: "Gibraltar Pound" },
+    CurrencyInfo { code: "GMD", numeric: Some(270), minor_units: Some(2), name: "Dalasi" },
+    CurrencyInfo { code: "GNF", numeric: Some(324), minor_units: Some(0), name: "Guinean Franc" },
+    CurrencyInfo { code: "GTQ", numeric: Some(320), minor_units: Some(2), name: "Quetzal" },
+    CurrencyInfo { code: "GYD", numeric: Some(328), minor_units: Some(2), name: "Guyana Dollar" },
+    CurrencyInfo { code: "HKD", numeric: Some(344), minor_units: Some(2), name: "Hong Kong Dollar" },
+    CurrencyInfo { code: "HNL", numeric: Some(340), minor_units: Some(2), name: "Lempira" },
+    CurrencyInfo { code: "HTG", numeric: Some(332), minor_units: Some(2), name: "Gourde" },
+    CurrencyInfo { code: "HUF", numeric: Some(348), minor_units: Some(2), name: "Forint" },
+    CurrencyInfo { code: "IDR", numeric: Some(360), minor_units: Some(2), name: "Rupiah" },
+    CurrencyInfo { code: "ILS", numeric: Some(376), minor_units: Some(2), name: "New Israeli Sheqel" },
+    CurrencyInfo { code: "IMP", numeric: None, minor_units: Some(2), name: "Manx Pound" },
+    CurrencyInfo { code: "INR", numeric: Some(356), minor_units: Some(2), name: "Indian Rupee" },
+    CurrencyInfo { code: "IQD", numeric: Some(368), minor_units: Some(3), name: "Iraqi Dinar" },
+    CurrencyInfo { code: "IRR", numeric: Some(364), minor_units: Some(2), name: "Iranian Rial" },
+    CurrencyInfo { code: "ISK", numeric: Some(352), minor_units: Some(0), name: "Iceland Krona" },
+    CurrencyInfo { code: "JEP", numeric: None, minor_units: Some(2), name: "Jersey Pound" },
+    CurrencyInfo { code: "JMD", numeric: Some(388), minor_units: Some(2), name: "Jamaican Dollar" },
+    CurrencyInfo { code: "JOD", numeric: Some(400), minor_units: Some(3), name: "Jordanian Dinar" },
+    CurrencyInfo { code: "JPY", numeric: Some(392), minor_units: Some(0), name: "Yen" },
+    CurrencyInfo { code: "KES", numeric: Some(404), minor_units: Some(2), name: "Kenyan Shilling" },
+    CurrencyInfo { code: "KGS", numeric: Some(417), minor_units: Some(2), name: "Som" },
+    CurrencyInfo { code: "KHR", numeric: Some(116), minor_units: Some(2), name: "Riel" },
+    CurrencyInfo { code: "KID", numeric: None, minor_units: Some(2), name: "Kiribati Dollar" },
+    CurrencyInfo { code: "KMF", numeric: Some(174), minor_units: Some(0), name: "Comorian Franc" },
+    CurrencyInfo { code: "KPW", numeric: Some(408), minor_units: Some(2), name: "North Korean Won" },
+    CurrencyInfo { code: "KRW", numeric: Some(410), minor_units: Some(0), name: "Won" },
+    CurrencyInfo { code: "KWD", numeric: Some(414), minor_units: Some(3), name: "Kuwaiti Dinar" },
+    CurrencyInfo { code: "KYD", numeric: Some(136), minor_units: Some(2), name: "Cayman Islands Dollar" },
+    CurrencyInfo { code: "KZT", numeric: Some(398), minor_units: Some(2), name: "Tenge" },
+    CurrencyInfo { code: "LAK", numeric: Some(418), minor_units: Some(2), name: "Lao Kip" },
+    CurrencyInfo { code: "LBP", numeric: Some(422), minor_units: Some(2), name: "Lebanese Pound" },
+    CurrencyInfo { code: "LKR", numeric: Some(144), minor_units: Some(2), name: "Sri Lanka Rupee" },
+    CurrencyInfo { code: "LRD", numeric: Some(430), minor_units: Some(2), name: "Liberian Dollar" },
+    CurrencyInfo { code: "LSL", numeric: Some(426), minor_units: Some(2), name: "Loti" },
+    CurrencyInfo { code: "LYD", numeric: Some(434), minor_units: Some(3), name: "Libyan Dinar" },
+    CurrencyInfo { code: "MAD", numeric: Some(504), minor_units: Some(2), name: "Moroccan Dirham" },
+    CurrencyInfo { code: "MDL", numeric: Some(498), minor_units: Some(2), name: "Moldovan Leu" },
+    CurrencyInfo { code: "MGA", numeric: Some(969), minor_units: Some(2), name: "Malagasy Ariary" },
+    CurrencyInfo { code: "MKD", numeric: Some(807), minor_units: Some(2), name: "Denar" },
+    CurrencyInfo { code: "MMK", numeric: Some(104), minor_units: Some(2), name: "Kyat" },
+    CurrencyInfo { code: "MNT", numeric: Some(496), minor_units: Some(2), name: "Tugrik" },
+    CurrencyInfo { code: "MOP", numeric: Some(446), minor_units: Some(2), name: "Pataca" },
+    CurrencyInfo { code: "MRU", numeric: Some(929), minor_units: Some(2), name: "Ouguiya" },
+    CurrencyInfo { code: "MUR", numeric: Some(480), minor_units: Some(2), name: "Mauritius Rupee" },
+    CurrencyInfo { code: "MVR", numeric: Some(462), minor_units: Some(2), name: "Rufiyaa" },
+    CurrencyInfo { code: "MWK", numeric: Some(454), minor_units: Some(2), name: "Malawi Kwacha" },
+    CurrencyInfo { code: "MXN", numeric: Some(484), minor_units: Some(2), name: "Mexican Peso" },
+    CurrencyInfo { code: "MXV", numeric: Some(979), minor_units: Some(2), name: "Mexican Unidad de Inversion (UDI)" },
+    CurrencyInfo { code: "MYR", numeric: Some(458), minor_units: Some(2), name: "Malaysian Ringgit" },
+    CurrencyInfo { code: "MZN", numeric: Some(943), minor_units: Some(2), name: "Mozambique Metical" },
+    CurrencyInfo { code: "NAD", numeric: Some(516), minor_units: Some(2), name: "Namibia Dollar" },
+    CurrencyInfo { code: "NGN", numeric: Some(566), minor_units: Some(2), name: "Naira" },
+    CurrencyInfo { code: "NIO", numeric: Some(558), minor_units: Some(2), name: "Cordoba Oro" },
+    CurrencyInfo { code: "NOK", numeric: Some(578), minor_units: Some(2), name: "Norwegian Krone" },
+    CurrencyInfo { code: "NPR", numeric: Some(524), minor_units: Some(2), name: "Nepalese Rupee" },
+    CurrencyInfo { code: "NZD", numeric: Some(554), minor_units: Some(2), name: "New Zealand Dollar" },
+    CurrencyInfo { code: "OMR", numeric: Some(512), minor_units: Some(3), name: "Rial Omani" },
+    CurrencyInfo { code: "PAB", numeric: Some(590), minor_units: Some(2), name: "Balboa" },
+    CurrencyInfo { code: "PEN", numeric: Some(604), minor_units: Some(2), name: "Sol" },
+    CurrencyInfo { code: "PGK", numeric: Some(598), minor_units: Some(2), name: "Kina" },
+    CurrencyInfo { code: "PHP", numeric: Some(608), minor_units: Some(2), name: "Philippine Peso" },
+    CurrencyInfo { code: "PKR", numeric: Some(586), minor_units: Some(2), name: "Pakistan Rupee" },
+    CurrencyInfo { code: "PLN", numeric: Some(985), minor_units: Some(2), name: "Zloty" },
+    CurrencyInfo { code: "PYG", numeric: Some(600), minor_units: Some(0), name: "Guarani" },
+    CurrencyInfo { code: "QAR", numeric: Some(634), minor_units: Some(2), name: "Qatari Rial" },
+    CurrencyInfo { code: "RON", numeric: Some(946), minor_units: Some(2), name: "Romanian Leu" },
+    CurrencyInfo { code: "RSD", numeric: Some(941), minor_units: Some(2), name: "Serbian Dinar" },
+    CurrencyInfo { code: "RUB", numeric: Some(643), minor_units: Some(2), name: "Russian Ruble" },
+    CurrencyInfo { code: "RWF", numeric: Some(646), minor_units: Some(0), name: "Rwanda Franc" },
+    CurrencyInfo { code: "SAR", numeric: Some(682), minor_units: Some(2), name: "Saudi Riyal" },
+    CurrencyInfo { code: "SBD", numeric: Some(90), minor_units: Some(2), name: "Solomon Islands Dollar" },
+    CurrencyInfo { code: "SCR", numeric: Some(690), minor_units: Some(2), name: "Seychelles Rupee" },
+    CurrencyInfo { code: "SDG", numeric: Some(938), minor_units: Some(2), name: "Sudanese Pound" },
+    CurrencyInfo { code: "SEK", numeric: Some(752), minor_units: Some(2), name: "Swedish Krona" },
+    CurrencyInfo { code: "SGD", numeric: Some(702), minor_units: Some(2), name: "Singapore Dollar" },
+    CurrencyInfo { code: "SHP", numeric: Some(654), minor_units: Some(2), name: "Saint Helena Pound" },
+    CurrencyInfo { code: "SLE", numeric: Some(925), minor_units: Some(2), name: "Leone" },
+    CurrencyInfo { code: "SLL", numeric: Some(694), minor_units: Some(2), name: "Leone (old)" },
+    CurrencyInfo { code: "SOS", numeric: Some(706), minor_units: Some(2), name: "Somali Shilling" },
+    CurrencyInfo { code: "SRD", numeric: Some(968), minor_units: Some(2), name: "Surinam Dollar" },
+    CurrencyInfo { code: "SSP", numeric: Some(728), minor_units: Some(2), name: "South Sudanese Pound" },
+    CurrencyInfo { code: "STN", numeric: Some(930), minor_units: Some(2), name: "Dobra" },
+    CurrencyInfo { code: "SVC", numeric: Some(222), minor_units: Some(2), name: "El Salvador Colon" },
+    CurrencyInfo { code: "SYP", numeric: Some(760), minor_units: Some(2), name: "Syrian Pound" },
+    CurrencyInfo { code: "SZL", numeric: Some(748), minor_units: Some(2), name: "Lilangeni" },
+    CurrencyInfo { code: "THB", numeric: Some(764), minor_units: Some(2), name: "Baht" },
+    CurrencyInfo { code: "TJS", numeric: Some(972), minor_units: Some(2), name: "Somoni" },
+    CurrencyInfo { code: "TMT", numeric: Some(934), minor_units: Some(2), name: "Turkmenistan New Manat" },
+    CurrencyInfo { code: "TND", numeric: Some(788), minor_units: Some(3), name: "Tunisian Dinar" },
+    CurrencyInfo { code: "TOP", numeric: Some(776), minor_units: Some(2), name: "Pa'anga" },
+    CurrencyInfo { code: "TRY", numeric: Some(949), minor_units: Some(2), name: "Turkish Lira" },
+    CurrencyInfo { code: "TTD", numeric: Some(780), minor_units: Some(2), name: "Trinidad and Tobago Dollar" },
+    CurrencyInfo { code: "TVD", numeric: None, minor_units: Some(2), name: "Tuvaluan Dollar" },
+    CurrencyInfo { code: "TWD", numeric: Some(901), minor_units: Some(2), name: "New Taiwan Dollar" },
+    CurrencyInfo { code: "TZS", numeric: Some(834), minor_units: Some(2), name: "Tanzanian Shilling" },
+    CurrencyInfo { code: "UAH", numeric: Some(980), minor_units: Some(2), name: "Hryvnia" },
+    CurrencyInfo { code: "UGX", numeric: Some(800), minor_units: Some(0), name: "Uganda Shilling" },
+    CurrencyInfo { code: "USD", numeric: Some(840), minor_units: Some(2), name: "US Dollar" },
+    CurrencyInfo { code: "USN", numeric: Some(997), minor_units: Some(2), name: "US Dollar (Next day)" },
+    CurrencyInfo { code: "UYI", numeric: Some(940), minor_units: Some(0), name: "Uruguay Peso en Unidades Indexadas (UI)" },
+    CurrencyInfo { code: "UYU", numeric: Some(858), minor_units: Some(2), name: "Peso Uruguayo" },
+    CurrencyInfo { code: "UYW", numeric: Some(927), minor_units: Some(4), name: "Unidad Previsional" },
+    CurrencyInfo { code: "UZS", numeric: Some(860), minor_units: Some(2), name: "Uzbekistan Sum" },
+    CurrencyInfo { code: "VED", numeric: Some(926), minor_units: Some(2), name: "Bolivar Soberano (digital)" },
+    CurrencyInfo { code: "VES", numeric: Some(928), minor_units: Some(2), name: "Bolivar Soberano" },
+    CurrencyInfo { code: "VND", numeric: Some(704), minor_units: Some(0), name: "Dong" },
+    CurrencyInfo { code: "VUV", numeric: Some(548), minor_units: Some(0), name: "Vatu" },
+    CurrencyInfo { code: "WST", numeric: Some(882), minor_units: Some(2), name: "Tala" },
+    CurrencyInfo { code: "XAF", numeric: Some(950), minor_units: Some(0), name: "CFA Franc BEAC" },
+    CurrencyInfo { code: "XAG", numeric: Some(961), minor_units: None, name: "Silver" },
+    CurrencyInfo { code: "XAU", numeric: Some(959), minor_units: None, name: "Gold" },
+    CurrencyInfo { code: "XCD", numeric: Some(951), minor_units: Some(2), name: "East Caribbean Dollar" },
+    CurrencyInfo { code: "XCG", numeric: Some(532), minor_units: Some(2), name: "Caribbean Guilder" },
+    CurrencyInfo { code: "XDR", numeric: Some(960), minor_units: None, name: "SDR (Special Drawing Right)" },
+    CurrencyInfo { code: "XOF", numeric: Some(952), minor_units: Some(0), name: "CFA Franc BCEAO" },
+    CurrencyInfo { code: "XPD", numeric: Some(964), minor_units: None, name: "Palladium" },
+    CurrencyInfo { code: "XPF", numeric: Some(953), minor_units: Some(0), name: "CFP Franc" },
+    CurrencyInfo { code: "XPT", numeric: Some(962), minor_units: None, name: "Platinum" },
+    CurrencyInfo { code: "XSU", numeric: Some(994), minor_units: None, name: "Sucre" },
+    CurrencyInfo { code: "XUA", numeric: Some(965), minor_units: None, name: "ADB Unit of Account" },
+    CurrencyInfo { code: "YER", numeric: Some(886), minor_units: Some(2), name: "Yemeni Rial" },
+    CurrencyInfo { code: "ZAR", numeric: Some(710), minor_units: Some(2), name: "Rand" },
+    CurrencyInfo { code: "ZMW", numeric: Some(967), minor_units: Some(2), name: "Zambian Kwacha" },
+    CurrencyInfo { code: "ZWG", numeric: Some(924), minor_units: Some(2), name: "Zimbabwe Gold" },
+    CurrencyInfo { code: "ZWL", numeric: Some(932), minor_units: Some(2), name: "Zimbabwe Dollar" },
+];
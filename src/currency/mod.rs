@@ -0,0 +1,159 @@
+mod iso4217;
+
+use crate::error::ExchangeRateError;
+
+use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+pub use iso4217::CURRENCIES;
+
+#[derive(Debug, PartialEq)]
+pub struct CurrencyInfo
+{
+    pub code: &'static str,
+    pub numeric: Option<u16>,
+    /// Number of digits after the decimal separator; `None` for units like gold or SDR.
+    pub minor_units: Option<u32>,
+    pub name: &'static str,
+}
+
+/// Three-letter currency code, validated against the embedded ISO 4217 table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CurrencyCode(&'static str);
+
+impl CurrencyCode
+{
+    pub const EUR: CurrencyCode = CurrencyCode("EUR");
+    pub const USD: CurrencyCode = CurrencyCode("USD");
+
+    pub fn as_str(&self) -> &'static str
+    {
+        self.0
+    }
+
+    pub fn info(&self) -> &'static CurrencyInfo
+    {
+        lookup(self.0).expect("CurrencyCode is always present in the currency table")
+    }
+
+    pub fn numeric(&self) -> Option<u16>
+    {
+        self.info().numeric
+    }
+
+    pub fn minor_units(&self) -> Option<u32>
+    {
+        self.info().minor_units
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        self.info().name
+    }
+
+    pub fn all() -> impl Iterator<Item = CurrencyCode>
+    {
+        CURRENCIES.iter().map(|currency_info| CurrencyCode(currency_info.code))
+    }
+}
+
+fn lookup(code: &str) -> Option<&'static CurrencyInfo>
+{
+    CURRENCIES
+        .binary_search_by(|currency_info| currency_info.code.cmp(code))
+        .ok()
+        .map(|index| &CURRENCIES[index])
+}
+
+impl FromStr for CurrencyCode
+{
+    type Err = ExchangeRateError;
+
+    /// Accepts codes in any letter case, surrounded by whitespace or not.
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let code = s.trim().to_ascii_uppercase();
+        match lookup(&code) {
+            Some(currency_info) => Ok(CurrencyCode(currency_info.code)),
+            None => Err(ExchangeRateError::InvalidCurrencyCode(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for CurrencyCode
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        f.write_str(self.0)
+    }
+}
+
+impl AsRef<str> for CurrencyCode
+{
+    fn as_ref(&self) -> &str
+    {
+        self.0
+    }
+}
+
+impl Serialize for CurrencyCode
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for CurrencyCode
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_sorted_and_unique() {
+        assert!(CURRENCIES.windows(2).all(|pair| pair[0].code < pair[1].code));
+    }
+
+    #[test]
+    fn parses_and_normalizes_codes() {
+        let code: CurrencyCode = " usd ".parse().unwrap();
+
+        assert_eq!(code.as_str(), "USD");
+        assert_eq!(code.numeric(), Some(840));
+        assert_eq!(code.minor_units(), Some(2));
+        assert_eq!(code.name(), "US Dollar");
+    }
+
+    #[test]
+    fn rejects_unknown_codes() {
+        for code in ["USDD", "US", "", "XYZ", "12A"] {
+            assert!(matches!(code.parse::<CurrencyCode>(), Err(ExchangeRateError::InvalidCurrencyCode(_))));
+        }
+    }
+
+    #[test]
+    fn minor_units_follow_iso_4217() {
+        assert_eq!(CurrencyCode::from_str("JPY").unwrap().minor_units(), Some(0));
+        assert_eq!(CurrencyCode::from_str("KWD").unwrap().minor_units(), Some(3));
+        assert_eq!(CurrencyCode::from_str("XAU").unwrap().minor_units(), None);
+    }
+
+    #[test]
+    fn serializes_as_plain_string() {
+        let code = CurrencyCode::from_str("pln").unwrap();
+        let json = serde_json::to_string(&code).unwrap();
+
+        assert_eq!(json, r#""PLN""#);
+        assert_eq!(serde_json::from_str::<CurrencyCode>(&json).unwrap(), code);
+        assert!(serde_json::from_str::<CurrencyCode>(r#""PLNN""#).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::currency::CurrencyCode;
+use crate::error::{check_status, ExchangeRateError};
+use crate::models::LatestRates;
+
+use chrono::{NaiveDate, NaiveTime};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+pub const DAILY_URL: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-daily.xml";
+/// Every publication since 1999; several megabytes.
+pub const HIST_URL: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-hist.xml";
+
+/// Euro foreign exchange reference rates published by the ECB for one day:
+/// how many units of each currency one euro buys.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EcbRates
+{
+    pub date: NaiveDate,
+    pub rates: HashMap<String, Decimal>,
+}
+
+impl EcbRates
+{
+    /// The same rate map as `ConversionRates` returns for EUR, including EUR itself;
+    /// the update time is the start of the reference day.
+    pub fn to_latest_rates(&self) -> LatestRates
+    {
+        let mut conversion_rates = self.rates.clone();
+        conversion_rates.insert(CurrencyCode::EUR.to_string(), Decimal::ONE);
+        LatestRates {
+            base: CurrencyCode::EUR,
+            conversion_rates,
+            time_last_update_unix: Some(self.date.and_time(NaiveTime::MIN).and_utc().timestamp()),
+            time_next_update_unix: None,
+        }
+    }
+}
+
+/// Parses `eurofxref-daily.xml`, returning the rates of the most recent day in the document.
+pub fn parse_daily(xml: &str) -> Result<EcbRates, ExchangeRateError>
+{
+    parse_days(xml)?
+        .into_iter()
+        .max_by_key(|ecb_rates| ecb_rates.date)
+        .ok_or_else(|| ExchangeRateError::InvalidResponse("ECB XML: no dated Cube element".to_string()))
+}
+
+/// Parses `eurofxref-hist.xml` (or any of the 90 days feeds), newest day first.
+pub fn parse_history(xml: &str) -> Result<Vec<EcbRates>, ExchangeRateError>
+{
+    let mut days = parse_days(xml)?;
+    days.sort_by_key(|ecb_rates| Reverse(ecb_rates.date));
+    Ok(days)
+}
+
+/// Rates in force on `date`: those of the latest publication on or before it,
+/// as there are none on weekends and TARGET holidays. `history` must be newest first.
+pub fn rates_on(history: &[EcbRates], date: NaiveDate) -> Option<&EcbRates>
+{
+    history.iter().find(|ecb_rates| ecb_rates.date <= date)
+}
+
+pub fn load_daily(path: &Path) -> Result<EcbRates, ExchangeRateError>
+{
+    parse_daily(&fs::read_to_string(path)?)
+}
+
+pub fn load_history(path: &Path) -> Result<Vec<EcbRates>, ExchangeRateError>
+{
+    parse_history(&fs::read_to_string(path)?)
+}
+
+pub async fn fetch_daily(http_client: &Client, url: &str) -> Result<EcbRates, ExchangeRateError>
+{
+    parse_daily(&fetch_text(http_client, url).await?)
+}
+
+pub async fn fetch_history(http_client: &Client, url: &str) -> Result<Vec<EcbRates>, ExchangeRateError>
+{
+    parse_history(&fetch_text(http_client, url).await?)
+}
+
+async fn fetch_text(http_client: &Client, url: &str) -> Result<String, ExchangeRateError>
+{
+    let response = check_status(http_client.get(url).send().await?)?;
+    Ok(response.text().await?)
+}
+
+fn parse_days(xml: &str) -> Result<Vec<EcbRates>, ExchangeRateError>
+{
+    let document = roxmltree::Document::parse(xml)
+        .map_err(|e| ExchangeRateError::InvalidResponse(format!("ECB XML: {}", e)))?;
+
+    document
+        .descendants()
+        .filter(|node| node.has_tag_name("Cube") && node.has_attribute("time"))
+        .map(parse_day)
+        .collect()
+}
+
+fn parse_day(day: roxmltree::Node) -> Result<EcbRates, ExchangeRateError>
+{
+    let time = day.attribute("time").unwrap_or_default();
+    let date = NaiveDate::parse_from_str(time, "%Y-%m-%d")
+        .map_err(|_| ExchangeRateError::InvalidResponse(format!("ECB XML: invalid date {}", time)))?;
+
+    let mut rates = HashMap::new();
+    for cube in day.children().filter(|node| node.has_tag_name("Cube")) {
+        if let (Some(currency), Some(rate)) = (cube.attribute("currency"), cube.attribute("rate")) {
+            let rate = Decimal::from_str(rate)
+                .map_err(|_| ExchangeRateError::InvalidResponse(format!("ECB XML: invalid rate {} for {}", rate, currency)))?;
+            rates.insert(currency.to_string(), rate);
+        }
+    }
+    Ok(EcbRates { date, rates })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAILY_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+	<gesmes:subject>Reference rates</gesmes:subject>
+	<gesmes:Sender>
+		<gesmes:name>European Central Bank</gesmes:name>
+	</gesmes:Sender>
+	<Cube>
+		<Cube time='2024-03-22'>
+			<Cube currency='USD' rate='1.0823'/>
+			<Cube currency='JPY' rate='163.77'/>
+			<Cube currency='PLN' rate='4.3178'/>
+		</Cube>
+	</Cube>
+</gesmes:Envelope>"#;
+
+    #[test]
+    fn parses_daily_reference_rates() {
+        let ecb_rates = parse_daily(DAILY_XML).unwrap();
+
+        assert_eq!(ecb_rates.date, NaiveDate::from_ymd_opt(2024, 3, 22).unwrap());
+        assert_eq!(ecb_rates.rates.len(), 3);
+        assert_eq!(ecb_rates.rates["PLN"], Decimal::from_str("4.3178").unwrap());
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        assert!(matches!(parse_daily("<Cube>"), Err(ExchangeRateError::InvalidResponse(_))));
+        assert!(matches!(parse_daily("<Cube><Cube time='2024-13-01'/></Cube>"), Err(ExchangeRateError::InvalidResponse(_))));
+        assert!(matches!(parse_daily("<Cube><Cube time='2024-03-22'><Cube currency='USD' rate='x'/></Cube></Cube>"), Err(ExchangeRateError::InvalidResponse(_))));
+    }
+
+    const HIST_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+	<Cube>
+		<Cube time="2024-03-22"><Cube currency="USD" rate="1.0823"/><Cube currency="PLN" rate="4.3178"/></Cube>
+		<Cube time="2024-03-21"><Cube currency="USD" rate="1.0907"/><Cube currency="PLN" rate="4.3020"/></Cube>
+		<Cube time="2024-03-18"><Cube currency="USD" rate="1.0872"/></Cube>
+	</Cube>
+</gesmes:Envelope>"#;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_history_newest_first() {
+        let history = parse_history(HIST_XML).unwrap();
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].date, date(2024, 3, 22));
+        assert_eq!(history[2].rates.len(), 1);
+        assert_eq!(history[1].rates["USD"], Decimal::from_str("1.0907").unwrap());
+    }
+
+    #[test]
+    fn daily_rates_are_the_newest_in_any_order() {
+        let xml = "<Cube><Cube time='2024-03-18'><Cube currency='USD' rate='1.0872'/></Cube>\
+            <Cube time='2024-03-22'><Cube currency='USD' rate='1.0823'/></Cube>\
+            <Cube time='2024-03-21'><Cube currency='USD' rate='1.0907'/></Cube></Cube>";
+
+        let ecb_rates = parse_daily(xml).unwrap();
+        assert_eq!(ecb_rates.date, date(2024, 3, 22));
+        assert_eq!(ecb_rates.rates["USD"], Decimal::from_str("1.0823").unwrap());
+    }
+
+    #[test]
+    fn finds_rates_in_force_on_a_date() {
+        let history = parse_history(HIST_XML).unwrap();
+
+        assert_eq!(rates_on(&history, date(2024, 3, 21)).unwrap().date, date(2024, 3, 21));
+        assert_eq!(rates_on(&history, date(2024, 3, 20)).unwrap().date, date(2024, 3, 18));
+        assert_eq!(rates_on(&history, date(2024, 3, 24)).unwrap().date, date(2024, 3, 22));
+        assert!(rates_on(&history, date(2024, 3, 17)).is_none());
+    }
+
+    #[test]
+    fn converts_to_latest_rates_against_euro() {
+        let latest_rates = parse_daily(DAILY_XML).unwrap().to_latest_rates();
+
+        assert_eq!(latest_rates.base, CurrencyCode::EUR);
+        assert_eq!(latest_rates.conversion_rates.len(), 4);
+        assert_eq!(latest_rates.conversion_rates["EUR"], Decimal::ONE);
+        assert_eq!(latest_rates.time_last_update_unix, Some(1711065600));
+    }
+
+    #[test]
+    fn loads_history_from_file() {
+        let path = std::env::temp_dir().join(format!("exchange_rate_api_ecb_hist_{}.xml", std::process::id()));
+        fs::write(&path, HIST_XML).unwrap();
+
+        assert_eq!(load_history(&path).unwrap().len(), 3);
+        assert_eq!(load_daily(&path).unwrap().date, date(2024, 3, 22));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(load_daily(&path), Err(ExchangeRateError::Io(_))));
+    }
+
+    #[tokio::test]
+    async fn fetches_history_from_url() {
+        use wiremock::{MockServer, Mock, ResponseTemplate};
+        use wiremock::matchers::{method, path};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/eurofxref-hist.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(HIST_XML))
+            .mount(&mock_server)
+            .await;
+
+        let history = fetch_history(&Client::new(), &format!("{}/eurofxref-hist.xml", mock_server.uri())).await.unwrap();
+        assert_eq!(history.len(), 3);
+        assert!(matches!(fetch_daily(&Client::new(), &format!("{}/missing.xml", mock_server.uri())).await, Err(ExchangeRateError::UnexpectedStatus(_))));
+    }
+}
@@ -0,0 +1,127 @@
+use core::fmt;
+use reqwest::{Response, StatusCode};
+
+#[derive(Debug)]
+pub enum ExchangeRateError
+{
+    UnsupportedCode,
+    MalformedRequest,
+    InvalidKey,
+    InactiveAccount,
+    QuotaReached,
+    RateLimited,
+    Server(StatusCode),
+    Transport(reqwest::Error),
+    Decode(reqwest::Error),
+    UnexpectedStatus(StatusCode),
+    InvalidCurrencyCode(String),
+    /// The converted amount does not fit in a `Decimal`.
+    AmountOverflow,
+    InvalidResponse(String),
+    UnknownProvider(String),
+    /// A file given on the command line could not be read as a whole.
+    InvalidInput(String),
+    Io(std::io::Error),
+    Database(rusqlite::Error),
+}
+
+impl ExchangeRateError
+{
+    /// Maps the `error-type` field of a failed API response to its variant.
+    pub fn from_error_type(error_type: Option<&str>, status_code: StatusCode) -> Self
+    {
+        match error_type {
+            Some("unsupported-code") => ExchangeRateError::UnsupportedCode,
+            Some("malformed-request") => ExchangeRateError::MalformedRequest,
+            Some("invalid-key") => ExchangeRateError::InvalidKey,
+            Some("inactive-account") => ExchangeRateError::InactiveAccount,
+            Some("quota-reached") => ExchangeRateError::QuotaReached,
+            _ => ExchangeRateError::UnexpectedStatus(status_code),
+        }
+    }
+}
+
+/// Maps unsuccessful statuses of the keyless backends, which carry no `error-type`.
+pub(crate) fn check_status(response: Response) -> Result<Response, ExchangeRateError>
+{
+    let status_code = response.status();
+    if status_code.is_success() {
+        Ok(response)
+    }
+    else if status_code.is_server_error() {
+        Err(ExchangeRateError::Server(status_code))
+    }
+    else {
+        Err(ExchangeRateError::UnexpectedStatus(status_code))
+    }
+}
+
+impl fmt::Display for ExchangeRateError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            ExchangeRateError::UnsupportedCode => write!(f, "Unsupported currency code."),
+            ExchangeRateError::MalformedRequest => write!(f, "Malformed request."),
+            ExchangeRateError::InvalidKey => write!(f, "Invalid API key."),
+            ExchangeRateError::InactiveAccount => write!(f, "Account is inactive."),
+            ExchangeRateError::QuotaReached => write!(f, "Request quota reached."),
+            ExchangeRateError::RateLimited => write!(f, "Request refused by the local rate limiter."),
+            ExchangeRateError::Server(status_code) => write!(f, "Server Error: {}", status_code),
+            ExchangeRateError::Transport(e) => write!(f, "Transport Error: {}", e),
+            ExchangeRateError::Decode(e) => write!(f, "Error decoding response: {}", e),
+            ExchangeRateError::UnexpectedStatus(status_code) => write!(f, "Unexpected response status: {}", status_code),
+            ExchangeRateError::InvalidCurrencyCode(code) => write!(f, "Invalid currency code: {}", code),
+            ExchangeRateError::AmountOverflow => write!(f, "The converted amount is too large."),
+            ExchangeRateError::InvalidResponse(message) => write!(f, "Invalid response: {}", message),
+            ExchangeRateError::UnknownProvider(name) => write!(f, "Unknown rate provider: {}", name),
+            ExchangeRateError::InvalidInput(message) => write!(f, "Invalid input: {}", message),
+            ExchangeRateError::Io(e) => write!(f, "I/O error: {}", e),
+            ExchangeRateError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExchangeRateError
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self
+        {
+            ExchangeRateError::Transport(e) | ExchangeRateError::Decode(e) => Some(e),
+            ExchangeRateError::Io(e) => Some(e),
+            ExchangeRateError::Database(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ExchangeRateError
+{
+    fn from(e: reqwest::Error) -> Self
+    {
+        if e.is_decode() {
+            ExchangeRateError::Decode(e)
+        }
+        else {
+            ExchangeRateError::Transport(e)
+        }
+    }
+}
+
+impl From<std::io::Error> for ExchangeRateError
+{
+    fn from(e: std::io::Error) -> Self
+    {
+        ExchangeRateError::Io(e)
+    }
+}
+
+impl From<rusqlite::Error> for ExchangeRateError
+{
+    fn from(e: rusqlite::Error) -> Self
+    {
+        ExchangeRateError::Database(e)
+    }
+}
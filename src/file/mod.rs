@@ -0,0 +1,38 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Writes `contents` to a temporary file next to `path` and renames it over `path`,
+/// so readers never see a partially written file.
+pub(crate) fn write_atomically(path: &Path, contents: &str) -> io::Result<()>
+{
+    let tmp_path = tmp_path(path)?;
+    if let Err(e) = fs::write(&tmp_path, contents).and_then(|_| fs::rename(&tmp_path, path)) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// `rates.json` becomes `rates.json.<pid>.tmp` in the same directory, so the rename stays on
+/// one file system, processes sharing the file never write the same temporary file and no
+/// other file, including one named like the temporary file, is touched.
+pub(crate) fn tmp_path(path: &Path) -> io::Result<PathBuf>
+{
+    let file_name = path.file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("{} is not a file name", path.display())))?;
+    let mut tmp_file_name = file_name.to_os_string();
+    tmp_file_name.push(format!(".{}.tmp", std::process::id()));
+    Ok(path.with_file_name(tmp_file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_the_temporary_file_after_the_target() {
+        assert_eq!(tmp_path(Path::new("/data/rates.json")).unwrap(), PathBuf::from(format!("/data/rates.json.{}.tmp", std::process::id())));
+        assert!(tmp_path(Path::new("/")).is_err());
+    }
+}
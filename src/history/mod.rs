@@ -0,0 +1,212 @@
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::LatestRates;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use rust_decimal::Decimal;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS rates (
+    base        TEXT    NOT NULL,
+    target      TEXT    NOT NULL,
+    rate        TEXT    NOT NULL,
+    last_update INTEGER NOT NULL,
+    provider    TEXT    NOT NULL,
+    PRIMARY KEY (provider, base, target, last_update)
+);
+CREATE INDEX IF NOT EXISTS rates_by_target ON rates (target, last_update);
+";
+
+/// Rate of `base` in `target` as published by `provider` at `last_update`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatePoint
+{
+    pub base: CurrencyCode,
+    pub target: CurrencyCode,
+    pub rate: Decimal,
+    pub last_update: DateTime<Utc>,
+    pub provider: String,
+}
+
+/// Time series of fetched rate tables in a SQLite database. A table is stored once
+/// per provider and update time, however often it is fetched.
+pub struct RateHistory
+{
+    connection: Mutex<Connection>,
+}
+
+impl RateHistory
+{
+    pub fn open(path: &Path) -> Result<Self, ExchangeRateError>
+    {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Self, ExchangeRateError>
+    {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, ExchangeRateError>
+    {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Stores every rate of the table and returns how many were new.
+    /// Tables without an update time cannot be deduplicated and are rejected.
+    pub fn record(&self, latest_rates: &LatestRates, provider: &str) -> Result<usize, ExchangeRateError>
+    {
+        let last_update = latest_rates.time_last_update_unix
+            .ok_or_else(|| ExchangeRateError::InvalidResponse("rate table has no update time".to_string()))?;
+        let base = latest_rates.base.as_str();
+
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        let mut inserted = 0;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT OR IGNORE INTO rates (base, target, rate, last_update, provider) VALUES (?1, ?2, ?3, ?4, ?5)")?;
+            // The base itself is always stored so that cross rates can be looked up through it.
+            inserted += statement.execute(params![base, base, Decimal::ONE.to_string(), last_update, provider])?;
+            for (target, rate) in &latest_rates.conversion_rates {
+                if target != base {
+                    inserted += statement.execute(params![base, target, rate.to_string(), last_update, provider])?;
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(inserted)
+    }
+
+    /// Rate in force at `at`: the most recent stored table published no later than `at`
+    /// that lists both currencies, whatever its base.
+    pub fn rate_at(&self, base: CurrencyCode, target: CurrencyCode, at: DateTime<Utc>) -> Result<Option<RatePoint>, ExchangeRateError>
+    {
+        Ok(self.query(base, target, at.timestamp(), Some(1))?.pop())
+    }
+
+    /// Every stored rate of the pair, oldest first, one per provider and update time.
+    pub fn series(&self, base: CurrencyCode, target: CurrencyCode) -> Result<Vec<RatePoint>, ExchangeRateError>
+    {
+        let mut rate_points = self.query(base, target, i64::MAX, None)?;
+        rate_points.reverse();
+        Ok(rate_points)
+    }
+
+    /// Newest first; a table based on `base` itself is preferred over cross rates from the same update.
+    fn query(&self, base: CurrencyCode, target: CurrencyCode, until_unix: i64, limit: Option<usize>) -> Result<Vec<RatePoint>, ExchangeRateError>
+    {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT from_rate.rate, to_rate.rate, from_rate.last_update, from_rate.provider
+             FROM rates AS from_rate
+             JOIN rates AS to_rate
+               ON to_rate.provider = from_rate.provider
+              AND to_rate.base = from_rate.base
+              AND to_rate.last_update = from_rate.last_update
+             WHERE from_rate.target = ?1 AND to_rate.target = ?2 AND from_rate.last_update <= ?3
+             ORDER BY from_rate.last_update DESC, from_rate.provider, from_rate.base = ?1 DESC")?;
+        let rows = statement.query_map(params![base.as_str(), target.as_str(), until_unix], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?, row.get::<_, String>(3)?))
+        })?;
+
+        let mut rate_points: Vec<RatePoint> = Vec::new();
+        for row in rows {
+            let (from_rate, to_rate, last_update, provider) = row?;
+            if rate_points.last().is_some_and(|previous| previous.last_update.timestamp() == last_update && previous.provider == provider) {
+                continue;
+            }
+            let rate = match parse_rate(&to_rate)?.checked_div(parse_rate(&from_rate)?) {
+                Some(rate) => rate.normalize(),
+                None => continue,
+            };
+            let last_update = DateTime::from_timestamp(last_update, 0)
+                .ok_or_else(|| ExchangeRateError::InvalidResponse(format!("invalid update time {}", last_update)))?;
+            rate_points.push(RatePoint { base, target, rate, last_update, provider });
+            if limit.is_some_and(|limit| rate_points.len() >= limit) {
+                break;
+            }
+        }
+        Ok(rate_points)
+    }
+}
+
+fn parse_rate(rate: &str) -> Result<Decimal, ExchangeRateError>
+{
+    Decimal::from_str(rate).map_err(|_| ExchangeRateError::InvalidResponse(format!("invalid stored rate {}", rate)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(code: &str) -> CurrencyCode {
+        CurrencyCode::from_str(code).unwrap()
+    }
+
+    fn latest_rates(base: &str, time_last_update_unix: i64, rates: &[(&str, &str)]) -> LatestRates {
+        LatestRates {
+            base: code(base),
+            conversion_rates: rates.iter().map(|(code, rate)| (code.to_string(), Decimal::from_str(rate).unwrap())).collect(),
+            time_last_update_unix: Some(time_last_update_unix),
+            time_next_update_unix: None,
+        }
+    }
+
+    fn at(timestamp: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(timestamp, 0).unwrap()
+    }
+
+    #[test]
+    fn deduplicates_on_update_time() {
+        let history = RateHistory::in_memory().unwrap();
+        let rates = latest_rates("USD", 1000, &[("USD", "1"), ("PLN", "4.0"), ("EUR", "0.8")]);
+
+        assert_eq!(history.record(&rates, "exchangerate-api").unwrap(), 3);
+        assert_eq!(history.record(&rates, "exchangerate-api").unwrap(), 0);
+        assert_eq!(history.record(&rates, "ecb").unwrap(), 3);
+        assert_eq!(history.record(&latest_rates("USD", 2000, &[("PLN", "4.1")]), "exchangerate-api").unwrap(), 2);
+        assert_eq!(history.series(code("USD"), code("PLN")).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn finds_rate_in_force_at_a_time() {
+        let history = RateHistory::in_memory().unwrap();
+        history.record(&latest_rates("USD", 1000, &[("PLN", "4.0"), ("EUR", "0.8")]), "exchangerate-api").unwrap();
+        history.record(&latest_rates("USD", 2000, &[("PLN", "4.2"), ("EUR", "0.84")]), "exchangerate-api").unwrap();
+
+        assert_eq!(history.rate_at(code("USD"), code("PLN"), at(999)).unwrap(), None);
+        assert_eq!(history.rate_at(code("USD"), code("PLN"), at(1999)).unwrap().unwrap().rate, Decimal::from_str("4.0").unwrap());
+
+        let rate_point = history.rate_at(code("USD"), code("PLN"), at(2000)).unwrap().unwrap();
+        assert_eq!(rate_point.rate, Decimal::from_str("4.2").unwrap());
+        assert_eq!(rate_point.last_update, at(2000));
+        assert_eq!(rate_point.provider, "exchangerate-api");
+    }
+
+    #[test]
+    fn derives_cross_and_inverse_rates() {
+        let history = RateHistory::in_memory().unwrap();
+        history.record(&latest_rates("USD", 1000, &[("PLN", "4.0"), ("EUR", "0.8")]), "exchangerate-api").unwrap();
+
+        assert_eq!(history.rate_at(code("EUR"), code("PLN"), at(1000)).unwrap().unwrap().rate, Decimal::from(5));
+        assert_eq!(history.rate_at(code("PLN"), code("USD"), at(1000)).unwrap().unwrap().rate, Decimal::from_str("0.25").unwrap());
+        assert_eq!(history.rate_at(code("PLN"), code("GBP"), at(1000)).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_tables_without_update_time() {
+        let history = RateHistory::in_memory().unwrap();
+        let mut rates = latest_rates("USD", 1000, &[("PLN", "4.0")]);
+        rates.time_last_update_unix = None;
+
+        assert!(matches!(history.record(&rates, "exchangerate-api"), Err(ExchangeRateError::InvalidResponse(_))));
+    }
+}
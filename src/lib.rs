@@ -1,3 +1,18 @@
 pub mod arguments;
+pub mod batch;
+pub mod cache;
+pub mod client;
+pub mod currency;
+pub mod ecb;
+pub mod error;
+mod file;
+pub mod history;
 pub mod models;
-pub mod request_handler;
\ No newline at end of file
+pub mod money;
+pub mod output;
+pub mod provider;
+pub mod rate_limit;
+pub mod rate_table;
+pub mod request_handler;
+pub mod retry;
+pub mod snapshot;
\ No newline at end of file
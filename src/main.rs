@@ -1,75 +1,325 @@
-mod arguments;
-mod request_handler;
-mod models;
-use crate::models::FetchResult;
-use crate::request_handler::ApiRequest;
+use exchange_rate_api::arguments::{self, parse_providers, parse_threshold, Cli, Command, GlobalOptions, EXIT_FAILURE};
+use exchange_rate_api::batch::{Batch, RatesKey};
+use exchange_rate_api::cache::ResponseCache;
+use exchange_rate_api::client::{ExchangeRateClient, OpenAccessClient, OPEN_ACCESS_ATTRIBUTION};
+use exchange_rate_api::currency::CurrencyCode;
+use exchange_rate_api::error::ExchangeRateError;
+use exchange_rate_api::history::RateHistory;
+use exchange_rate_api::models::LatestRates;
+use exchange_rate_api::output::{OutputFormat, Records};
+use exchange_rate_api::provider::{conversions_from, ConsensusProvider, FailoverProvider, OfflineProvider, ProviderKind, RateProvider};
+use exchange_rate_api::request_handler::ApiRequest;
+use exchange_rate_api::snapshot::RatesSnapshot;
 
+use chrono::{DateTime, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::env;
-use tokio;
+use std::error::Error;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::ExitCode;
+
+type CliResult = Result<(), Box<dyn Error>>;
 
 #[tokio::main]
 
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    match arguments::parse() {
-        Ok((source, target, decimal_value)) => {
+async fn main() -> ExitCode {
+    let cli = match arguments::parse() {
+        Ok(cli) => cli,
+        Err(e) if e.exit_code() == 0 => {
+            print!("{}", e);
+            return ExitCode::SUCCESS;
+        },
+        Err(e) => {
+            eprint!("{}", e);
+            return ExitCode::from(e.exit_code());
+        },
+    };
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(e.downcast_ref::<ExchangeRateError>().map_or(EXIT_FAILURE, arguments::exit_code))
+        },
+    }
+}
+
+/// Where the rates come from: --offline, then --provider/$RATE_PROVIDER,
+/// then the API with $API_KEY, and finally the keyless open access endpoint.
+enum Backend
+{
+    Keyed(ExchangeRateClient),
+    OpenAccess(OpenAccessClient),
+    Offline(OfflineProvider),
+    Failover(FailoverProvider),
+    Consensus(ConsensusProvider, Decimal),
+}
 
-            let api_key = match env::var_os("API_KEY")
-            {
-                Some(api_key_string) => api_key_string.into_string().unwrap(),
-                None => panic!("$API_KEY is not set")
+impl Backend
+{
+    fn from_options(options: &GlobalOptions) -> Result<Self, Box<dyn Error>> {
+        if let Some(snapshot_path) = &options.offline {
+            let provider = OfflineProvider::load(snapshot_path)?;
+            if provider.is_stale(Utc::now()) {
+                if let Some(next_update) = provider.next_update() {
+                    eprintln!("Warning: the snapshot is out of date; newer rates were published at {}", next_update);
+                }
+            }
+            return Ok(Backend::Offline(provider));
+        }
+
+        let api_key = env::var("API_KEY").ok().filter(|api_key| !api_key.is_empty());
+        let provider_kinds = match (&options.providers, env::var("RATE_PROVIDER")) {
+            (Some(provider_kinds), _) => provider_kinds.clone(),
+            (None, Ok(provider_names)) => parse_providers(&provider_names)?,
+            (None, Err(_)) => vec![ProviderKind::ExchangeRateApi],
+        };
+        if provider_kinds != [ProviderKind::ExchangeRateApi] {
+            let providers = provider_kinds.iter()
+                .map(|provider_kind| provider_kind.build(api_key.as_deref()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let divergence_threshold = match (options.divergence_threshold, env::var("RATE_DIVERGENCE_THRESHOLD")) {
+                (Some(divergence_threshold), _) => divergence_threshold,
+                (None, Ok(divergence_threshold)) => parse_threshold(&divergence_threshold)?,
+                (None, Err(_)) => return Ok(Backend::Failover(FailoverProvider::new(providers))),
             };
+            let provider = ConsensusProvider::new(providers).with_divergence_threshold(divergence_threshold);
+            return Ok(Backend::Consensus(provider, divergence_threshold));
+        }
 
-            if !source.is_empty() && !target.is_empty() && !decimal_value.is_zero()
-            {
-                let prefix = "https://v6.exchangerate-api.com/".to_string();
-                let request = request_handler::StandardRequest::new(&source, &target, &decimal_value, &api_key, &prefix);
-                match request.fetch().await {
-                    Ok(Some(FetchResult::Decimal(convertion_result))) => {
-                        println!("Conversion from {} -> {} = {:?}", source, target, convertion_result);
-                    },
-                    Ok(None) => {
-                        println!("Incorrect Source/Target");
-                    },
-                    Err(e) => {
-                        eprintln!("Error fetching conversion rate: {}", e);
-                    },
-                    _ => (),
+        let cache = match env::var_os("API_CACHE_DIR") {
+            Some(cache_dir) => Some(ResponseCache::on_disk(cache_dir.into())?),
+            None => None,
+        };
+        match api_key {
+            Some(api_key) => {
+                let mut client_builder = ExchangeRateClient::builder(&api_key);
+                if let Some(cache) = cache {
+                    client_builder = client_builder.cache(cache);
                 }
-            } 
-            else if source.is_empty() && target.is_empty() && decimal_value.is_zero()
-            {
-                let prefix = "https://v6.exchangerate-api.com/".to_string();
-                let request = request_handler::SupportedCodes::new(&api_key, &prefix);
-                match request.fetch().await {
-                    Ok(Some(FetchResult::VecString(supported_codes))) => {
-                        for currency_pair in supported_codes {
-                            println!("Currency Code: {:?}, Currency Name: {:?}", currency_pair[0], currency_pair[1]);
-                        }
-                    },
-                    Ok(None) => println!("No data available."),
-                    Err(e) => eprintln!("Error: {}", e),
-                    _ => (),
+                Ok(Backend::Keyed(client_builder.build()?))
+            },
+            None => {
+                let mut client_builder = OpenAccessClient::builder();
+                if let Some(cache) = cache {
+                    client_builder = client_builder.cache(cache);
                 }
+                Ok(Backend::OpenAccess(client_builder.build()?))
+            },
+        }
+    }
+
+    fn provider(&self) -> &dyn RateProvider {
+        match self {
+            Backend::Keyed(client) => client,
+            Backend::OpenAccess(client) => client,
+            Backend::Offline(provider) => provider,
+            Backend::Failover(provider) => provider,
+            Backend::Consensus(provider, _) => provider,
+        }
+    }
+
+    /// Quota, enriched and historical data are only served by the API itself.
+    fn client(&self, command: &str) -> Result<&ExchangeRateClient, Box<dyn Error>> {
+        match self {
+            Backend::Keyed(client) => Ok(client),
+            _ => Err(format!("the {} command needs $API_KEY and the exchangerate-api provider", command).into()),
+        }
+    }
+
+    /// Fetches the latest rates against `base`; with $RATE_HISTORY_DB set, they are added
+    /// to that SQLite database under the name of the provider that served them.
+    async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError> {
+        let (provider_name, latest_rates) = match self {
+            // Rates served by --offline were recorded when they were fetched, and a table of
+            // medians was not served by any one provider.
+            Backend::Offline(_) | Backend::Consensus(..) => return self.provider().latest_rates(base).await,
+            Backend::Failover(provider) => match provider.served_latest_rates(base).await? {
+                Some((provider_name, latest_rates)) => (provider_name, latest_rates),
+                None => return Ok(None),
+            },
+            _ => match self.provider().latest_rates(base).await? {
+                Some(latest_rates) => (self.provider().name(), latest_rates),
+                None => return Ok(None),
+            },
+        };
+        if let Some(path) = env::var_os("RATE_HISTORY_DB") {
+            if let Err(e) = RateHistory::open(Path::new(&path)).and_then(|history| history.record(&latest_rates, provider_name)) {
+                eprintln!("Warning: the rates were not recorded: {}", e);
             }
-            else if !source.is_empty()  && target.is_empty() && decimal_value.is_zero() {
-                let prefix = "https://v6.exchangerate-api.com/".to_string();
-                let request = request_handler::ConversionRates::new(&source, &api_key, &prefix);
-                match request.fetch().await {
-                    Ok(Some(FetchResult::HashMapRates(conversion_rates))) => {
-                        for element in conversion_rates
-                        {
-                            println!("Currency {},  convertion rate {}",element.0, element.1);
-                        }
-                    }
-                    Ok(None) => println!("No data available."),
-                    Err(e) => eprintln!("Error: {}", e),
-                    _ => (),
-                }
+        }
+        Ok(Some(latest_rates))
+    }
+}
+
+/// Chooses the backend on first use, so that commands reading local data need no provider.
+struct LazyBackend
+{
+    options: GlobalOptions,
+    backend: Option<Backend>,
+}
+
+impl LazyBackend
+{
+    fn get(&mut self) -> Result<&Backend, Box<dyn Error>> {
+        let backend = match self.backend.take() {
+            Some(backend) => backend,
+            None => Backend::from_options(&self.options)?,
+        };
+        Ok(self.backend.insert(backend))
+    }
+}
+
+async fn run(cli: Cli) -> CliResult {
+    let mut options = cli.options;
+    let output = options.output;
+    let sort = options.sort;
+    let filter = options.filter.take();
+
+    let mut backend = LazyBackend { options, backend: None };
+    let mut records = fetch_records(cli.command, &mut backend).await?;
+    if let Some(codes) = filter {
+        records.retain_codes(&codes)?;
+    }
+    if let Some(order) = sort {
+        records.sort(order)?;
+    }
+    print!("{}", records.render(output));
+
+    // The terms of use of the keyless endpoint require attribution, kept off stdout
+    // unless the results are meant for reading.
+    if let Some(Backend::OpenAccess(_)) = backend.backend {
+        match output {
+            OutputFormat::Table => println!("{}", OPEN_ACCESS_ATTRIBUTION),
+            _ => eprintln!("{}", OPEN_ACCESS_ATTRIBUTION),
+        }
+    }
+    Ok(())
+}
+
+async fn fetch_records(command: Command, backend: &mut LazyBackend) -> Result<Records, Box<dyn Error>> {
+    Ok(match command {
+        Command::Convert { source, target, amount } => convert(backend.get()?, source, target, amount).await?,
+        Command::ConvertMany { amount, source, targets } => {
+            let latest_rates = backend.get()?.latest_rates(source).await?.ok_or("No data available.")?;
+            Records::conversions(&conversions_from(&latest_rates, targets.as_deref(), amount)?, amount)
+        },
+        Command::Rates { source } => {
+            let latest_rates = backend.get()?.latest_rates(source).await?.ok_or("No data available.")?;
+            Records::latest_rates(&latest_rates)
+        },
+        Command::Codes => {
+            let supported_codes = backend.get()?.provider().supported_codes().await?.ok_or("No data available.")?;
+            Records::supported_codes(&supported_codes)
+        },
+        Command::Quota => {
+            let quota_status = backend.get()?.client("quota")?.quota_request().fetch().await?.ok_or("No data available.")?;
+            Records::quota(&quota_status)
+        },
+        Command::Enriched { source, target } => {
+            let enriched_conversion = backend.get()?.client("enriched")?.enriched_request(&source, &target).fetch().await?.ok_or("No data available.")?;
+            Records::enriched(&enriched_conversion)
+        },
+        Command::Historical { source, date, amount } => {
+            let request = backend.get()?.client("historical")?.historical_rates(&source, &date, amount.as_ref());
+            let dated_rates = request.fetch().await?.ok_or("No data available.")?;
+            Records::historical(&dated_rates, amount)?
+        },
+        Command::Snapshot { output: path, bases } => {
+            let backend = backend.get()?;
+            let mut rates = Vec::with_capacity(bases.len());
+            for base in bases {
+                rates.push(backend.latest_rates(base).await?.ok_or_else(|| format!("No rates for {}.", base))?);
             }
+            let snapshot = RatesSnapshot::new(rates.clone());
+            snapshot.write(&path)?;
+            Records::snapshot(&path.display().to_string(), &rates)
         },
-        Err(e) => {
-            eprint!("{}", e);
+        Command::Batch { input } => {
+            let content = match input.to_str() {
+                Some("-") => {
+                    let mut content = String::new();
+                    io::stdin().read_to_string(&mut content)?;
+                    content
+                },
+                _ => std::fs::read_to_string(&input)?,
+            };
+            let batch = Batch::read(&content)?;
+            let backend = backend.get()?;
+            let mut rates = HashMap::new();
+            for rates_key in batch.rates_keys() {
+                rates.insert(rates_key, batch_rates(backend, rates_key).await.map_err(|e| e.to_string()));
+            }
+            let columns = batch.columns().to_vec();
+            let results = batch.convert(&rates);
+            let failures = results.iter().filter(|result| result.conversion.is_err()).count();
+            if failures > 0 {
+                eprintln!("Warning: {} of {} rows could not be converted", failures, results.len());
+            }
+            Records::batch(&columns, &results)
+        },
+        // Recorded rates are read from the local database only.
+        Command::History { base, target, at } => recorded_rates(base, target, at)?,
+    })
+}
+
+/// With several providers agreeing on a rate, their quotes and the spread are reported on stderr.
+async fn convert(backend: &Backend, source: CurrencyCode, target: CurrencyCode, amount: Decimal) -> Result<Records, Box<dyn Error>> {
+    if let Backend::Consensus(provider, divergence_threshold) = backend {
+        let consensus = provider.consensus_rate(source, target).await?.ok_or("No data available.")?;
+        for quote in &consensus.quotes {
+            eprintln!("Rate from {}: {}", quote.provider, quote.rate);
+        }
+        for (provider_name, e) in &consensus.failures {
+            eprintln!("Error from {}: {}", provider_name, e);
         }
+        eprintln!("Spread: {} ({}%)", consensus.spread, (consensus.relative_spread() * Decimal::ONE_HUNDRED).round_dp(4));
+        if consensus.divergent {
+            eprintln!("Warning: the rates diverge by more than {}%", divergence_threshold * Decimal::ONE_HUNDRED);
+        }
+        return Ok(Records::conversions(&[consensus.conversion(amount)?], amount));
     }
-    Ok(())
+
+    // The keyed API converts a pair itself; elsewhere the conversion comes from the table
+    // of rates, which is recorded like any other.
+    let conversions = match backend {
+        Backend::Keyed(client) => vec![client.pair_conversion(source, target, amount).await?.ok_or("No data available.")?],
+        _ => {
+            let latest_rates = backend.latest_rates(source).await?.ok_or("No data available.")?;
+            conversions_from(&latest_rates, Some(&[target]), amount)?
+        },
+    };
+    Ok(Records::conversions(&conversions, amount))
+}
+
+/// The latest rates against `base`, or with a date, the rates of that day from the API.
+async fn batch_rates(backend: &Backend, (base, date): RatesKey) -> Result<LatestRates, Box<dyn Error>> {
+    let Some(date) = date else {
+        return Ok(backend.latest_rates(base).await?.ok_or("No data available.")?);
+    };
+    let client = backend.client("batch").map_err(|_| "rates of past dates need $API_KEY and the exchangerate-api provider")?;
+    let dated_rates = client.historical_rates(&base, &date, None).fetch().await?.ok_or("No data available.")?;
+    Ok(LatestRates {
+        base,
+        conversion_rates: dated_rates.conversion_rates.ok_or("No data available.")?,
+        time_last_update_unix: Some(date.and_time(NaiveTime::MIN).and_utc().timestamp()),
+        time_next_update_unix: None,
+    })
+}
+
+fn recorded_rates(base: CurrencyCode, target: CurrencyCode, at: Option<DateTime<Utc>>) -> Result<Records, Box<dyn Error>> {
+    let path = env::var_os("RATE_HISTORY_DB")
+        .ok_or("$RATE_HISTORY_DB is not set; it names the database the fetched rates are recorded in.")?;
+    let history = RateHistory::open(Path::new(&path))?;
+
+    let rate_points: Vec<_> = match at {
+        Some(at) => history.rate_at(base, target, at)?.into_iter().collect(),
+        None => history.series(base, target)?,
+    };
+    if rate_points.is_empty() {
+        return Err(format!("No recorded rates for {} -> {}.", base, target).into());
+    }
+    Ok(Records::history(&rate_points))
 }
@@ -1,8 +1,11 @@
 use serde::{Serialize,Deserialize};
+use crate::currency::CurrencyCode;
+use crate::money::Money;
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiResponse 
 {
     pub result: String,
@@ -13,16 +16,123 @@ pub struct ApiResponse
     pub time_next_update_unix: Option<i64>,
     pub time_next_update_utc: Option<String>,
     pub base_code: Option<String>,
+    pub target_code: Option<String>,
+    /// Named `rates` by the open access endpoint.
+    #[serde(alias = "rates")]
     pub conversion_rates: Option<HashMap<String, Decimal>>,
+    pub conversion_amounts: Option<HashMap<String, Decimal>>,
     pub supported_codes: Option<Vec<Vec<String>>>,
+    pub target_data: Option<TargetData>,
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+    pub plan_quota: Option<u64>,
+    pub requests_remaining: Option<u64>,
+    pub refresh_day_of_month: Option<u32>,
+    pub conversion_rate: Option<Decimal>,
     pub conversion_result: Option<Decimal>,
     #[serde(rename = "error-type")]
     pub error_type: Option<String>,
 }
 
-#[derive(Debug)]
-pub enum FetchResult {
-    Decimal(Decimal),
-    VecString(Vec<Vec<String>>),
-    HashMapRates(HashMap<String, Decimal>)
-}
\ No newline at end of file
+/// Result of `StandardRequest`: `result` is the requested amount of `base` converted into `target`
+/// at `conversion_rate`, the rate published by the API at `time_last_update_unix`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairConversion
+{
+    pub base: CurrencyCode,
+    pub target: CurrencyCode,
+    pub conversion_rate: Decimal,
+    pub result: Money,
+    pub time_last_update_unix: Option<i64>,
+    pub time_next_update_unix: Option<i64>,
+}
+
+impl PairConversion
+{
+    pub fn last_update(&self) -> Option<DateTime<Utc>>
+    {
+        DateTime::from_timestamp(self.time_last_update_unix?, 0)
+    }
+
+    pub fn next_update(&self) -> Option<DateTime<Utc>>
+    {
+        DateTime::from_timestamp(self.time_next_update_unix?, 0)
+    }
+}
+
+/// Details about the target currency returned by the enriched endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetData
+{
+    pub locale: String,
+    pub two_letter_code: String,
+    pub currency_name: String,
+    pub currency_name_short: String,
+    /// Unicode code points of the symbol in hex, comma separated, e.g. `0024` or `0041,0024`.
+    pub display_symbol: String,
+    pub flag_url: String,
+}
+
+impl TargetData
+{
+    /// Decodes `display_symbol` into the symbol itself, e.g. `$` for `0024`.
+    pub fn symbol(&self) -> Option<String>
+    {
+        self.display_symbol
+            .split(',')
+            .map(|code_point| u32::from_str_radix(code_point.trim(), 16).ok().and_then(char::from_u32))
+            .collect()
+    }
+}
+
+/// Result of `EnrichedRequest`: the rate from `base` to `target` with details about the target currency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichedConversion
+{
+    pub base: CurrencyCode,
+    pub target: CurrencyCode,
+    pub conversion_rate: Decimal,
+    pub target_data: TargetData,
+    pub time_last_update_unix: Option<i64>,
+    pub time_next_update_unix: Option<i64>,
+}
+
+/// Result of `ConversionRates`: how many units of each currency one unit of `base` buys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatestRates
+{
+    pub base: CurrencyCode,
+    pub conversion_rates: HashMap<String, Decimal>,
+    pub time_last_update_unix: Option<i64>,
+    pub time_next_update_unix: Option<i64>,
+}
+
+/// Result of `HistoricalRates`: rates of `base` on `date`, or, when an amount was requested,
+/// that amount of `base` converted into every currency in `conversion_amounts`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatedRates
+{
+    pub base: CurrencyCode,
+    pub date: NaiveDate,
+    pub conversion_rates: Option<HashMap<String, Decimal>>,
+    pub conversion_amounts: Option<HashMap<String, Decimal>>,
+}
+
+/// Result of `QuotaRequest`: the plan's monthly quota and how much of it is left.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuotaStatus
+{
+    pub plan_quota: u64,
+    pub requests_remaining: u64,
+    pub refresh_day_of_month: u32,
+}
+
+/// One entry of `SupportedCodes`. The code is kept as returned by the API,
+/// which may list currencies newer than the embedded ISO 4217 table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SupportedCurrency
+{
+    pub code: String,
+    pub name: String,
+}
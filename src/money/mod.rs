@@ -0,0 +1,105 @@
+use crate::currency::CurrencyCode;
+
+use core::fmt;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingStrategy
+{
+    /// Round half to even (banker's rounding), the default for financial amounts.
+    #[default]
+    HalfEven,
+    /// Round half away from zero.
+    HalfUp,
+    /// Drop the digits beyond the currency's minor units.
+    Truncate,
+}
+
+impl From<RoundingStrategy> for rust_decimal::RoundingStrategy
+{
+    fn from(rounding_strategy: RoundingStrategy) -> Self
+    {
+        match rounding_strategy {
+            RoundingStrategy::HalfEven => rust_decimal::RoundingStrategy::MidpointNearestEven,
+            RoundingStrategy::HalfUp => rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+            RoundingStrategy::Truncate => rust_decimal::RoundingStrategy::ToZero,
+        }
+    }
+}
+
+/// An amount in a given currency; the amount keeps full precision until it is rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Money
+{
+    pub amount: Decimal,
+    pub currency: CurrencyCode,
+}
+
+impl Money
+{
+    pub fn new(amount: Decimal, currency: CurrencyCode) -> Self
+    {
+        Self {
+            amount,
+            currency,
+        }
+    }
+
+    /// Rounds the amount to the currency's minor units (e.g. 0 for JPY, 3 for KWD).
+    /// Currencies without minor units, such as XAU, are returned unchanged.
+    pub fn round(&self, rounding_strategy: RoundingStrategy) -> Money
+    {
+        match self.currency.minor_units() {
+            Some(minor_units) => {
+                let mut amount = self.amount.round_dp_with_strategy(minor_units, rounding_strategy.into());
+                amount.rescale(minor_units);
+                Money::new(amount, self.currency)
+            },
+            None => *self,
+        }
+    }
+}
+
+impl fmt::Display for Money
+{
+    /// Formats the amount rounded half to even, followed by the currency code, e.g. `1994.35 PLN`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{} {}", self.round(RoundingStrategy::HalfEven).amount, self.currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn money(amount: &str, currency: &str) -> Money {
+        Money::new(Decimal::from_str(amount).unwrap(), CurrencyCode::from_str(currency).unwrap())
+    }
+
+    #[test]
+    fn rounds_to_currency_minor_units() {
+        assert_eq!(money("1234.5678", "JPY").round(RoundingStrategy::HalfEven).amount, Decimal::from_str("1235").unwrap());
+        assert_eq!(money("1.23456", "KWD").round(RoundingStrategy::HalfEven).amount, Decimal::from_str("1.235").unwrap());
+        assert_eq!(money("1.23456", "XAU").round(RoundingStrategy::HalfEven).amount, Decimal::from_str("1.23456").unwrap());
+    }
+
+    #[test]
+    fn rounding_strategies_differ_on_midpoints() {
+        let amount = money("2.345", "USD");
+
+        assert_eq!(amount.round(RoundingStrategy::HalfEven).amount, Decimal::from_str("2.34").unwrap());
+        assert_eq!(amount.round(RoundingStrategy::HalfUp).amount, Decimal::from_str("2.35").unwrap());
+        assert_eq!(money("2.349", "USD").round(RoundingStrategy::Truncate).amount, Decimal::from_str("2.34").unwrap());
+        assert_eq!(money("-2.345", "USD").round(RoundingStrategy::HalfUp).amount, Decimal::from_str("-2.35").unwrap());
+    }
+
+    #[test]
+    fn displays_with_fixed_minor_units() {
+        assert_eq!(money("1994.3", "PLN").to_string(), "1994.30 PLN");
+        assert_eq!(money("100", "KWD").to_string(), "100.000 KWD");
+        assert_eq!(money("99.5", "JPY").to_string(), "100 JPY");
+    }
+}
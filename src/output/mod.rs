@@ -0,0 +1,719 @@
+use crate::batch::BatchResult;
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::history::RatePoint;
+use crate::models::{DatedRates, EnrichedConversion, LatestRates, PairConversion, QuotaStatus, SupportedCurrency};
+use crate::money::{Money, RoundingStrategy};
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use core::fmt;
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// How command results are written to standard output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat
+{
+    /// Aligned columns for reading in a terminal.
+    #[default]
+    Table,
+    /// An array of objects, one per row, with numbers written exactly.
+    Json,
+    /// RFC 4180 comma separated values with a header row.
+    Csv,
+    /// Tab separated values with a header row.
+    Tsv,
+}
+
+impl OutputFormat
+{
+    pub const ALL: [OutputFormat; 4] = [OutputFormat::Table, OutputFormat::Json, OutputFormat::Csv, OutputFormat::Tsv];
+
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+        }
+    }
+}
+
+impl FromStr for OutputFormat
+{
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err>
+    {
+        OutputFormat::ALL.into_iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| name.to_string())
+    }
+}
+
+impl fmt::Display for OutputFormat
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Column the rows are sorted by; `Code` is the target currency where there is one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey
+{
+    Code,
+    Name,
+    Rate,
+}
+
+impl SortKey
+{
+    fn columns(&self) -> &'static [&'static str]
+    {
+        match self {
+            SortKey::Code => &["target", "code"],
+            SortKey::Name => &["name"],
+            SortKey::Rate => &["rate"],
+        }
+    }
+
+    /// Name of the column in messages.
+    pub fn column(&self) -> &'static str
+    {
+        self.columns()[0]
+    }
+
+    /// Index of the key's column among `columns`, if there is one.
+    pub fn position<S: AsRef<str>>(&self, columns: &[S]) -> Option<usize>
+    {
+        self.columns().iter().find_map(|name| columns.iter().position(|column| column.as_ref() == *name))
+    }
+}
+
+/// Parsed from `code`, `name` or `rate`, optionally followed by `:asc` or `:desc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortOrder
+{
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl FromStr for SortOrder
+{
+    type Err = String;
+
+    fn from_str(order: &str) -> Result<Self, Self::Err>
+    {
+        let (key, direction) = order.split_once(':').unwrap_or((order, "asc"));
+        let key = match key.to_ascii_lowercase().as_str() {
+            "code" => SortKey::Code,
+            "name" => SortKey::Name,
+            "rate" => SortKey::Rate,
+            _ => return Err(order.to_string()),
+        };
+        let descending = match direction.to_ascii_lowercase().as_str() {
+            "asc" => false,
+            "desc" => true,
+            _ => return Err(order.to_string()),
+        };
+        Ok(SortOrder { key, descending })
+    }
+}
+
+/// A single value of a result row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell
+{
+    Text(String),
+    Decimal(Decimal),
+    Integer(i64),
+    /// Written as RFC 3339 in UTC, e.g. `2024-03-22T00:00:01Z`.
+    Time(DateTime<Utc>),
+    Date(NaiveDate),
+    Null,
+}
+
+impl Cell
+{
+    fn time(unix: Option<i64>) -> Cell
+    {
+        unix.and_then(|unix| DateTime::from_timestamp(unix, 0)).map_or(Cell::Null, Cell::Time)
+    }
+
+    fn is_numeric(&self) -> bool
+    {
+        matches!(self, Cell::Decimal(_) | Cell::Integer(_))
+    }
+
+    /// Numbers compare by value and everything else by its text; empty cells come last.
+    fn compare(&self, other: &Cell) -> Ordering
+    {
+        match (self, other) {
+            (Cell::Null, Cell::Null) => Ordering::Equal,
+            (Cell::Null, _) => Ordering::Greater,
+            (_, Cell::Null) => Ordering::Less,
+            (Cell::Decimal(a), Cell::Decimal(b)) => a.cmp(b),
+            (Cell::Integer(a), Cell::Integer(b)) => a.cmp(b),
+            (a, b) => a.to_string().cmp(&b.to_string()),
+        }
+    }
+}
+
+impl From<&str> for Cell
+{
+    fn from(text: &str) -> Self
+    {
+        Cell::Text(text.to_string())
+    }
+}
+
+impl From<CurrencyCode> for Cell
+{
+    fn from(code: CurrencyCode) -> Self
+    {
+        Cell::Text(code.to_string())
+    }
+}
+
+impl From<Decimal> for Cell
+{
+    fn from(decimal: Decimal) -> Self
+    {
+        Cell::Decimal(decimal)
+    }
+}
+
+impl fmt::Display for Cell
+{
+    /// Null is written as an empty string.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self {
+            Cell::Text(text) => f.write_str(text),
+            Cell::Decimal(decimal) => write!(f, "{}", decimal),
+            Cell::Integer(integer) => write!(f, "{}", integer),
+            Cell::Time(time) => f.write_str(&time.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            Cell::Date(date) => write!(f, "{}", date),
+            Cell::Null => Ok(()),
+        }
+    }
+}
+
+/// Rows of a command result under a fixed set of column names.
+///
+/// Every command has its own columns, which stay the same whatever the data,
+/// so the output can be loaded by `jq`, spreadsheets and other tools. Batch results
+/// keep the columns of their input in front of their own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Records
+{
+    columns: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Records
+{
+    pub const CONVERSION_COLUMNS: &'static [&'static str] =
+        &["base", "target", "amount", "rate", "result", "last_update", "next_update"];
+    pub const RATES_COLUMNS: &'static [&'static str] = &["base", "target", "name", "rate", "last_update", "next_update"];
+    pub const CODES_COLUMNS: &'static [&'static str] = &["code", "name"];
+    pub const QUOTA_COLUMNS: &'static [&'static str] = &["plan_quota", "requests_remaining", "refresh_day_of_month"];
+    pub const ENRICHED_COLUMNS: &'static [&'static str] = &["base", "target", "rate", "currency_name", "currency_name_short",
+        "symbol", "locale", "two_letter_code", "flag_url", "last_update", "next_update"];
+    pub const HISTORICAL_COLUMNS: &'static [&'static str] = &["base", "date", "target", "rate", "amount", "result"];
+    pub const SNAPSHOT_COLUMNS: &'static [&'static str] = &["file", "base", "currencies", "last_update", "next_update"];
+    pub const HISTORY_COLUMNS: &'static [&'static str] = &["base", "target", "rate", "last_update", "provider"];
+    /// Appended to the columns of the batch input.
+    pub const BATCH_COLUMNS: &'static [&'static str] = &["rate", "result", "last_update", "error"];
+
+    pub fn new(columns: &[&str]) -> Self
+    {
+        Self {
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Panics if the row does not have a cell for every column.
+    pub fn push(&mut self, row: Vec<Cell>)
+    {
+        assert_eq!(row.len(), self.columns.len(), "a row must have a cell for every column");
+        self.rows.push(row);
+    }
+
+    pub fn columns(&self) -> &[String]
+    {
+        &self.columns
+    }
+
+    pub fn rows(&self) -> &[Vec<Cell>]
+    {
+        &self.rows
+    }
+
+    /// `amount` of `base` converted into each `target` at `rate`, the results rounded half to even.
+    pub fn conversions(conversions: &[PairConversion], amount: Decimal) -> Self
+    {
+        let mut records = Self::new(Self::CONVERSION_COLUMNS);
+        for conversion in conversions {
+            records.push(vec![
+                conversion.base.into(),
+                conversion.target.into(),
+                amount.into(),
+                conversion.conversion_rate.into(),
+                conversion.result.round(RoundingStrategy::HalfEven).amount.into(),
+                Cell::time(conversion.time_last_update_unix),
+                Cell::time(conversion.time_next_update_unix),
+            ]);
+        }
+        records
+    }
+
+    /// One row per currency, sorted by code, named from the currency table so that
+    /// no request beyond the rates is spent on names.
+    pub fn latest_rates(latest_rates: &LatestRates) -> Self
+    {
+        let mut records = Self::new(Self::RATES_COLUMNS);
+        for (target, rate) in sorted(&latest_rates.conversion_rates) {
+            let name = target.parse::<CurrencyCode>().map_or(Cell::Null, |code| code.name().into());
+            records.push(vec![
+                latest_rates.base.into(),
+                target.as_str().into(),
+                name,
+                (*rate).into(),
+                Cell::time(latest_rates.time_last_update_unix),
+                Cell::time(latest_rates.time_next_update_unix),
+            ]);
+        }
+        records
+    }
+
+    pub fn supported_codes(supported_codes: &[SupportedCurrency]) -> Self
+    {
+        let mut records = Self::new(Self::CODES_COLUMNS);
+        for supported_currency in supported_codes {
+            records.push(vec![supported_currency.code.as_str().into(), supported_currency.name.as_str().into()]);
+        }
+        records
+    }
+
+    pub fn quota(quota_status: &QuotaStatus) -> Self
+    {
+        let mut records = Self::new(Self::QUOTA_COLUMNS);
+        records.push(vec![
+            Cell::Integer(quota_status.plan_quota as i64),
+            Cell::Integer(quota_status.requests_remaining as i64),
+            Cell::Integer(quota_status.refresh_day_of_month.into()),
+        ]);
+        records
+    }
+
+    pub fn enriched(enriched_conversion: &EnrichedConversion) -> Self
+    {
+        let target_data = &enriched_conversion.target_data;
+        let mut records = Self::new(Self::ENRICHED_COLUMNS);
+        records.push(vec![
+            enriched_conversion.base.into(),
+            enriched_conversion.target.into(),
+            enriched_conversion.conversion_rate.into(),
+            target_data.currency_name.as_str().into(),
+            target_data.currency_name_short.as_str().into(),
+            Cell::Text(target_data.symbol().unwrap_or_else(|| target_data.display_symbol.clone())),
+            target_data.locale.as_str().into(),
+            target_data.two_letter_code.as_str().into(),
+            target_data.flag_url.as_str().into(),
+            Cell::time(enriched_conversion.time_last_update_unix),
+            Cell::time(enriched_conversion.time_next_update_unix),
+        ]);
+        records
+    }
+
+    /// One row per currency, sorted by code. With an amount, `rate` is left empty
+    /// as the API returns the converted amounts only. Results are rounded half to even,
+    /// and the records fail if a converted amount overflows.
+    pub fn historical(dated_rates: &DatedRates, amount: Option<Decimal>) -> Result<Self, ExchangeRateError>
+    {
+        let mut records = Self::new(Self::HISTORICAL_COLUMNS);
+        let row = |target: &str, rate: Cell, result: Cell| vec![
+            dated_rates.base.into(),
+            Cell::Date(dated_rates.date),
+            target.into(),
+            rate,
+            amount.map_or(Cell::Null, Cell::Decimal),
+            result,
+        ];
+        // Rounded like `conversions`, to the minor units of the target where it is known.
+        let rounded = |target: &str, result: Decimal| match target.parse::<CurrencyCode>() {
+            Ok(code) => Cell::Decimal(Money::new(result, code).round(RoundingStrategy::HalfEven).amount),
+            Err(_) => Cell::Decimal(result),
+        };
+        if let Some(conversion_amounts) = &dated_rates.conversion_amounts {
+            for (target, result) in sorted(conversion_amounts) {
+                records.push(row(target, Cell::Null, rounded(target, *result)));
+            }
+        }
+        else if let Some(conversion_rates) = &dated_rates.conversion_rates {
+            for (target, rate) in sorted(conversion_rates) {
+                let result = match amount {
+                    Some(amount) => rounded(target, amount.checked_mul(*rate).ok_or(ExchangeRateError::AmountOverflow)?),
+                    None => Cell::Null,
+                };
+                records.push(row(target, (*rate).into(), result));
+            }
+        }
+        Ok(records)
+    }
+
+    /// One row per base currency saved to `file`.
+    pub fn snapshot(file: &str, rates: &[LatestRates]) -> Self
+    {
+        let mut records = Self::new(Self::SNAPSHOT_COLUMNS);
+        for latest_rates in rates {
+            records.push(vec![
+                file.into(),
+                latest_rates.base.into(),
+                Cell::Integer(latest_rates.conversion_rates.len() as i64),
+                Cell::time(latest_rates.time_last_update_unix),
+                Cell::time(latest_rates.time_next_update_unix),
+            ]);
+        }
+        records
+    }
+
+    pub fn history(rate_points: &[RatePoint]) -> Self
+    {
+        let mut records = Self::new(Self::HISTORY_COLUMNS);
+        for rate_point in rate_points {
+            records.push(vec![
+                rate_point.base.into(),
+                rate_point.target.into(),
+                rate_point.rate.into(),
+                Cell::Time(rate_point.last_update),
+                rate_point.provider.as_str().into(),
+            ]);
+        }
+        records
+    }
+
+    /// Every field of the input under its `columns` as written, followed by the conversion
+    /// or the reason it failed.
+    pub fn batch(columns: &[String], results: &[BatchResult]) -> Self
+    {
+        let mut records = Self {
+            columns: columns.iter().cloned().chain(Self::BATCH_COLUMNS.iter().map(|column| column.to_string())).collect(),
+            rows: Vec::new(),
+        };
+        for result in results {
+            let (rate, converted, last_update, error) = match &result.conversion {
+                Ok(conversion) => (
+                    conversion.rate.into(),
+                    conversion.result.round(RoundingStrategy::HalfEven).amount.into(),
+                    conversion.last_update.map_or(Cell::Null, Cell::Time),
+                    Cell::Null,
+                ),
+                Err(e) => (Cell::Null, Cell::Null, Cell::Null, e.as_str().into()),
+            };
+            let mut cells: Vec<Cell> = columns.iter().enumerate()
+                .map(|(index, column)| result.row.field(index, column).map_or(Cell::Null, Cell::Text))
+                .collect();
+            cells.extend([rate, converted, last_update, error]);
+            records.push(cells);
+        }
+        records
+    }
+
+    /// Keeps the rows of the given currencies only.
+    pub fn retain_codes(&mut self, codes: &[CurrencyCode]) -> Result<(), String>
+    {
+        let column = self.column_of(SortKey::Code)?;
+        self.rows.retain(|row| codes.iter().any(|code| row[column] == Cell::from(*code)));
+        Ok(())
+    }
+
+    /// Stable sort; rows without a value in the column stay last in either direction.
+    pub fn sort(&mut self, order: SortOrder) -> Result<(), String>
+    {
+        let column = self.column_of(order.key)?;
+        self.rows.sort_by(|a, b| match (&a[column], &b[column]) {
+            (Cell::Null, _) | (_, Cell::Null) => a[column].compare(&b[column]),
+            _ if order.descending => b[column].compare(&a[column]),
+            _ => a[column].compare(&b[column]),
+        });
+        Ok(())
+    }
+
+    fn column_of(&self, key: SortKey) -> Result<usize, String>
+    {
+        key.position(&self.columns).ok_or_else(|| format!("the results have no {} column", key.column()))
+    }
+
+    /// Writes the records in `format`, ending with a newline.
+    pub fn render(&self, format: OutputFormat) -> String
+    {
+        match format {
+            OutputFormat::Table => self.render_table(),
+            OutputFormat::Json => self.render_json(),
+            OutputFormat::Csv => self.render_separated(',', csv_field),
+            OutputFormat::Tsv => self.render_separated('\t', tsv_field),
+        }
+    }
+
+    /// Text is aligned left and numbers right, with the decimal points of a column
+    /// lined up; empty cells are shown as `-`.
+    fn render_table(&self) -> String
+    {
+        let mut cells: Vec<Vec<String>> = self.rows.iter()
+            .map(|row| row.iter().map(|cell| match cell {
+                Cell::Null => "-".to_string(),
+                cell => cell.to_string(),
+            }).collect())
+            .collect();
+        for column in 0..self.columns.len() {
+            align_decimal_points(self.rows.iter().zip(cells.iter_mut()).map(|(row, row_cells)| (&row[column], &mut row_cells[column])));
+        }
+        let widths: Vec<usize> = self.columns.iter().enumerate()
+            .map(|(i, column)| cells.iter().map(|row| row[i].chars().count()).fold(column.len(), usize::max))
+            .collect();
+
+        let mut table = String::new();
+        let mut write_line = |fields: Vec<String>| {
+            table.push_str(fields.join("  ").trim_end());
+            table.push('\n');
+        };
+        write_line(self.columns.iter().zip(&widths).map(|(column, width)| format!("{:<width$}", column.to_uppercase())).collect());
+        for (row, row_cells) in self.rows.iter().zip(&cells) {
+            write_line(row.iter().zip(row_cells).zip(&widths).map(|((cell, text), width)| match cell.is_numeric() {
+                true => format!("{:>width$}", text),
+                false => format!("{:<width$}", text),
+            }).collect());
+        }
+        table
+    }
+
+    /// Decimals are written as JSON numbers with every digit kept.
+    fn render_json(&self) -> String
+    {
+        let objects: Vec<String> = self.rows.iter()
+            .map(|row| {
+                let members: Vec<String> = self.columns.iter().zip(row)
+                    .map(|(column, cell)| format!("{}:{}", json_string(column), json_value(cell)))
+                    .collect();
+                format!("  {{{}}}", members.join(","))
+            })
+            .collect();
+        match objects.is_empty() {
+            true => "[]\n".to_string(),
+            false => format!("[\n{}\n]\n", objects.join(",\n")),
+        }
+    }
+
+    fn render_separated(&self, separator: char, field: fn(&str) -> String) -> String
+    {
+        let mut separated = String::new();
+        let lines = std::iter::once(self.columns.iter().map(|column| field(column)).collect::<Vec<_>>())
+            .chain(self.rows.iter().map(|row| row.iter().map(|cell| field(&cell.to_string())).collect()));
+        for fields in lines {
+            separated.push_str(&fields.join(&separator.to_string()));
+            separated.push('\n');
+        }
+        separated
+    }
+}
+
+/// Pads the decimals of a column so that their points line up, e.g. `1.5` and `10.25`
+/// become ` 1.5 ` and `10.25`.
+fn align_decimal_points<'a>(column: impl Iterator<Item = (&'a Cell, &'a mut String)>)
+{
+    let decimals: Vec<&mut String> = column.filter(|(cell, _)| matches!(cell, Cell::Decimal(_))).map(|(_, text)| text).collect();
+    let split = |text: &str| text.find('.').map_or(text.len(), |point| point);
+    let integer_width = decimals.iter().map(|text| split(text)).max().unwrap_or(0);
+    let fraction_width = decimals.iter().map(|text| text.len() - split(text)).max().unwrap_or(0);
+    for text in decimals {
+        let (integer, fraction) = text.split_at(split(text));
+        *text = format!("{:>integer_width$}{:<fraction_width$}", integer, fraction);
+    }
+}
+
+fn sorted(rates: &HashMap<String, Decimal>) -> Vec<(&String, &Decimal)>
+{
+    let mut rates: Vec<_> = rates.iter().collect();
+    rates.sort_by_key(|(code, _)| *code);
+    rates
+}
+
+fn json_string(text: &str) -> String
+{
+    serde_json::to_string(text).expect("strings always serialize")
+}
+
+fn json_value(cell: &Cell) -> String
+{
+    match cell {
+        Cell::Decimal(_) | Cell::Integer(_) => cell.to_string(),
+        Cell::Null => "null".to_string(),
+        cell => json_string(&cell.to_string()),
+    }
+}
+
+/// Quotes fields holding a separator, quote or line break.
+fn csv_field(field: &str) -> String
+{
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+/// Tab separated values cannot be quoted, so tabs and line breaks become spaces.
+fn tsv_field(field: &str) -> String
+{
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money::Money;
+
+    fn pair_conversion() -> PairConversion {
+        PairConversion {
+            base: CurrencyCode::USD,
+            target: CurrencyCode::EUR,
+            conversion_rate: Decimal::from_str("0.9215").unwrap(),
+            result: Money::new(Decimal::from_str("92.150").unwrap(), CurrencyCode::EUR),
+            time_last_update_unix: Some(1711065601),
+            time_next_update_unix: None,
+        }
+    }
+
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!(OutputFormat::from_str("JSON"), Ok(OutputFormat::Json));
+        assert_eq!(OutputFormat::from_str("xml"), Err("xml".to_string()));
+        for format in OutputFormat::ALL {
+            assert_eq!(format.to_string().parse(), Ok(format));
+        }
+    }
+
+    #[test]
+    fn renders_every_format() {
+        let records = Records::conversions(&[pair_conversion()], Decimal::from(100));
+
+        assert_eq!(records.render(OutputFormat::Json), "[\n  {\"base\":\"USD\",\"target\":\"EUR\",\"amount\":100,\"rate\":0.9215,\
+\"result\":92.15,\"last_update\":\"2024-03-22T00:00:01Z\",\"next_update\":null}\n]\n");
+        assert_eq!(records.render(OutputFormat::Csv),
+            "base,target,amount,rate,result,last_update,next_update\nUSD,EUR,100,0.9215,92.15,2024-03-22T00:00:01Z,\n");
+        assert_eq!(records.render(OutputFormat::Tsv),
+            "base\ttarget\tamount\trate\tresult\tlast_update\tnext_update\nUSD\tEUR\t100\t0.9215\t92.15\t2024-03-22T00:00:01Z\t\n");
+        assert_eq!(records.render(OutputFormat::Table), "\
+BASE  TARGET  AMOUNT  RATE    RESULT  LAST_UPDATE           NEXT_UPDATE
+USD   EUR        100  0.9215   92.15  2024-03-22T00:00:01Z  -
+");
+    }
+
+    #[test]
+    fn keeps_the_columns_of_empty_results() {
+        let records = Records::supported_codes(&[]);
+
+        assert_eq!(records.render(OutputFormat::Json), "[]\n");
+        assert_eq!(records.render(OutputFormat::Csv), "code,name\n");
+    }
+
+    #[test]
+    fn escapes_separators() {
+        let records = Records::supported_codes(&[SupportedCurrency { code: "BAM".to_string(), name: "Convertible \"Mark\", BiH".to_string() }]);
+
+        assert_eq!(records.render(OutputFormat::Csv), "code,name\nBAM,\"Convertible \"\"Mark\"\", BiH\"\n");
+        assert_eq!(records.render(OutputFormat::Json), "[\n  {\"code\":\"BAM\",\"name\":\"Convertible \\\"Mark\\\", BiH\"}\n]\n");
+    }
+
+    #[test]
+    fn sorts_rates_by_code() {
+        let latest_rates = LatestRates {
+            base: CurrencyCode::USD,
+            conversion_rates: HashMap::from([
+                ("PLN".to_string(), Decimal::from_str("3.98").unwrap()),
+                ("EUR".to_string(), Decimal::from_str("0.92").unwrap()),
+                ("USD".to_string(), Decimal::ONE),
+            ]),
+            time_last_update_unix: None,
+            time_next_update_unix: None,
+        };
+
+        let targets: Vec<String> = Records::latest_rates(&latest_rates).rows().iter().map(|row| row[1].to_string()).collect();
+        assert_eq!(targets, ["EUR", "PLN", "USD"]);
+    }
+
+    fn latest_rates() -> LatestRates {
+        LatestRates {
+            base: CurrencyCode::USD,
+            conversion_rates: HashMap::from([
+                ("JPY".to_string(), Decimal::from_str("151.27").unwrap()),
+                ("EUR".to_string(), Decimal::from_str("0.9215").unwrap()),
+                ("PLN".to_string(), Decimal::from_str("3.98").unwrap()),
+            ]),
+            time_last_update_unix: None,
+            time_next_update_unix: None,
+        }
+    }
+
+    fn column(records: &Records, name: &str) -> Vec<String> {
+        let column = records.columns().iter().position(|column| column == name).unwrap();
+        records.rows().iter().map(|row| row[column].to_string()).collect()
+    }
+
+    #[test]
+    fn names_currencies_from_the_currency_table() {
+        let records = Records::latest_rates(&latest_rates());
+        assert_eq!(column(&records, "name"), ["Euro", "Yen", "Zloty"]);
+    }
+
+    #[test]
+    fn sorts_and_filters_rows() {
+        let mut records = Records::latest_rates(&latest_rates());
+
+        records.sort("rate:desc".parse().unwrap()).unwrap();
+        assert_eq!(column(&records, "target"), ["JPY", "PLN", "EUR"]);
+        records.sort("name".parse().unwrap()).unwrap();
+        assert_eq!(column(&records, "target"), ["EUR", "JPY", "PLN"]);
+        records.retain_codes(&["PLN".parse().unwrap(), CurrencyCode::EUR]).unwrap();
+        assert_eq!(column(&records, "target"), ["EUR", "PLN"]);
+
+        assert!(Records::supported_codes(&[]).sort("rate".parse().unwrap()).is_err());
+        assert!(SortOrder::from_str("rate:up").is_err());
+        assert!(SortOrder::from_str("symbol").is_err());
+    }
+
+    #[test]
+    fn historical_amounts_fail_on_overflow() {
+        let dated_rates = DatedRates {
+            base: CurrencyCode::USD,
+            date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            conversion_rates: Some(HashMap::from([("PLN".to_string(), Decimal::from(4))])),
+            conversion_amounts: None,
+        };
+
+        let records = Records::historical(&dated_rates, Some(Decimal::from(10))).unwrap();
+        assert_eq!(records.rows[0][5], Cell::Decimal(Decimal::from(40)));
+        let records = Records::historical(&dated_rates, Some(Decimal::from_str("0.333333").unwrap())).unwrap();
+        assert_eq!(records.rows[0][5].to_string(), "1.33");
+        assert!(matches!(Records::historical(&dated_rates, Some(Decimal::MAX)), Err(ExchangeRateError::AmountOverflow)));
+    }
+
+    #[test]
+    fn aligns_decimal_points() {
+        let records = Records::latest_rates(&latest_rates());
+
+        assert_eq!(records.render(OutputFormat::Table), "\
+BASE  TARGET  NAME   RATE      LAST_UPDATE  NEXT_UPDATE
+USD   EUR     Euro     0.9215  -            -
+USD   JPY     Yen    151.27    -            -
+USD   PLN     Zloty    3.98    -            -
+");
+    }
+}
@@ -0,0 +1,389 @@
+use super::RateProvider;
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::{LatestRates, PairConversion, SupportedCurrency};
+use crate::money::Money;
+
+use async_trait::async_trait;
+use futures::future::{join_all, BoxFuture};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// Relative spread above which a `Consensus` is flagged as divergent: 1%.
+pub const DEFAULT_DIVERGENCE_THRESHOLD: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+
+/// Asks its providers in priority order, moving on to the next one whenever a provider fails
+/// or has no data; the error of the last provider is returned when none succeeds.
+pub struct FailoverProvider
+{
+    providers: Vec<Box<dyn RateProvider>>,
+}
+
+impl FailoverProvider
+{
+    pub fn new(providers: Vec<Box<dyn RateProvider>>) -> Self
+    {
+        Self {
+            providers,
+        }
+    }
+
+    /// Like `latest_rates`, together with the name of the provider that served them.
+    pub async fn served_latest_rates(&self, base: CurrencyCode) -> Result<Option<(&'static str, LatestRates)>, ExchangeRateError>
+    {
+        first_success(&self.providers, |provider| Box::pin(async move {
+            Ok(provider.latest_rates(base).await?.map(|latest_rates| (provider.name(), latest_rates)))
+        })).await
+    }
+}
+
+#[async_trait]
+impl RateProvider for FailoverProvider
+{
+    fn name(&self) -> &'static str
+    {
+        "failover"
+    }
+
+    async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError>
+    {
+        Ok(self.served_latest_rates(base).await?.map(|(_, latest_rates)| latest_rates))
+    }
+
+    async fn pair_conversion(&self, base: CurrencyCode, target: CurrencyCode, amount: Decimal) -> Result<Option<PairConversion>, ExchangeRateError>
+    {
+        first_success(&self.providers, |provider| provider.pair_conversion(base, target, amount)).await
+    }
+
+    async fn supported_codes(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError>
+    {
+        first_success(&self.providers, |provider| provider.supported_codes()).await
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderQuote
+{
+    pub provider: &'static str,
+    pub rate: Decimal,
+}
+
+/// Rate of `base` in `target` agreed on by several providers.
+#[derive(Debug)]
+pub struct Consensus
+{
+    pub base: CurrencyCode,
+    pub target: CurrencyCode,
+    pub median: Decimal,
+    /// Difference between the highest and the lowest quote.
+    pub spread: Decimal,
+    /// Set when the spread relative to the median exceeds the divergence threshold.
+    pub divergent: bool,
+    pub quotes: Vec<ProviderQuote>,
+    pub failures: Vec<(&'static str, ExchangeRateError)>,
+    /// Update times of the oldest quote.
+    pub time_last_update_unix: Option<i64>,
+    pub time_next_update_unix: Option<i64>,
+}
+
+impl Consensus
+{
+    pub fn relative_spread(&self) -> Decimal
+    {
+        self.spread.checked_div(self.median).unwrap_or_default()
+    }
+
+    /// Converts `amount` of `base` into `target` at the median rate.
+    pub fn conversion(&self, amount: Decimal) -> Result<PairConversion, ExchangeRateError>
+    {
+        let converted = amount.checked_mul(self.median).ok_or(ExchangeRateError::AmountOverflow)?;
+        Ok(PairConversion {
+            base: self.base,
+            target: self.target,
+            conversion_rate: self.median,
+            result: Money::new(converted, self.target),
+            time_last_update_unix: self.time_last_update_unix,
+            time_next_update_unix: self.time_next_update_unix,
+        })
+    }
+}
+
+/// Queries all of its providers concurrently and reports the median of their rates.
+/// Providers that fail are listed in the result as long as at least one of them answers.
+pub struct ConsensusProvider
+{
+    providers: Vec<Box<dyn RateProvider>>,
+    divergence_threshold: Decimal,
+}
+
+impl ConsensusProvider
+{
+    pub fn new(providers: Vec<Box<dyn RateProvider>>) -> Self
+    {
+        Self {
+            providers,
+            divergence_threshold: DEFAULT_DIVERGENCE_THRESHOLD,
+        }
+    }
+
+    /// Relative spread, e.g. `0.005` for 0.5%, above which the consensus is flagged as divergent.
+    pub fn with_divergence_threshold(mut self, divergence_threshold: Decimal) -> Self
+    {
+        self.divergence_threshold = divergence_threshold;
+        self
+    }
+
+    pub async fn consensus_rate(&self, base: CurrencyCode, target: CurrencyCode) -> Result<Option<Consensus>, ExchangeRateError>
+    {
+        let results = join_all(self.providers.iter()
+            .map(|provider| provider.pair_conversion(base, target, Decimal::ONE))).await;
+
+        let mut quotes = Vec::new();
+        let mut failures = Vec::new();
+        let mut time_last_update_unix = None;
+        let mut time_next_update_unix = None;
+        for (provider, result) in self.providers.iter().zip(results) {
+            match result {
+                Ok(Some(pair_conversion)) => {
+                    quotes.push(ProviderQuote { provider: provider.name(), rate: pair_conversion.conversion_rate });
+                    time_last_update_unix = earliest(time_last_update_unix, pair_conversion.time_last_update_unix);
+                    time_next_update_unix = earliest(time_next_update_unix, pair_conversion.time_next_update_unix);
+                },
+                Ok(None) => {},
+                Err(e) => failures.push((provider.name(), e)),
+            }
+        }
+
+        let mut rates: Vec<Decimal> = quotes.iter().map(|quote| quote.rate).collect();
+        let median = match median(&mut rates) {
+            Some(median) => median,
+            None => return match failures.pop() {
+                Some((_, e)) => Err(e),
+                None => Ok(None),
+            },
+        };
+        let spread = rates[rates.len() - 1] - rates[0];
+
+        let mut consensus = Consensus {
+            base,
+            target,
+            median,
+            spread,
+            divergent: false,
+            quotes,
+            failures,
+            time_last_update_unix,
+            time_next_update_unix,
+        };
+        consensus.divergent = consensus.relative_spread() > self.divergence_threshold;
+        Ok(Some(consensus))
+    }
+}
+
+#[async_trait]
+impl RateProvider for ConsensusProvider
+{
+    fn name(&self) -> &'static str
+    {
+        "consensus"
+    }
+
+    /// Median of each currency over the providers that list it; update times are those of the oldest answer.
+    async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError>
+    {
+        let results = join_all(self.providers.iter().map(|provider| provider.latest_rates(base))).await;
+
+        let mut quotes: HashMap<String, Vec<Decimal>> = HashMap::new();
+        let mut time_last_update_unix = None;
+        let mut time_next_update_unix = None;
+        let mut last_error = None;
+        for result in results {
+            match result {
+                Ok(Some(latest_rates)) => {
+                    for (code, rate) in latest_rates.conversion_rates {
+                        quotes.entry(code).or_default().push(rate);
+                    }
+                    time_last_update_unix = earliest(time_last_update_unix, latest_rates.time_last_update_unix);
+                    time_next_update_unix = earliest(time_next_update_unix, latest_rates.time_next_update_unix);
+                },
+                Ok(None) => {},
+                Err(e) => last_error = Some(e),
+            }
+        }
+        if quotes.is_empty() {
+            return match last_error {
+                Some(e) => Err(e),
+                None => Ok(None),
+            };
+        }
+
+        Ok(Some(LatestRates {
+            base,
+            conversion_rates: quotes.into_iter()
+                .filter_map(|(code, mut rates)| Some((code, median(&mut rates)?)))
+                .collect(),
+            time_last_update_unix,
+            time_next_update_unix,
+        }))
+    }
+
+    async fn pair_conversion(&self, base: CurrencyCode, target: CurrencyCode, amount: Decimal) -> Result<Option<PairConversion>, ExchangeRateError>
+    {
+        self.consensus_rate(base, target).await?
+            .map(|consensus| consensus.conversion(amount))
+            .transpose()
+    }
+
+    /// Codes are not voted on; the first provider that lists them wins.
+    async fn supported_codes(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError>
+    {
+        first_success(&self.providers, |provider| provider.supported_codes()).await
+    }
+}
+
+async fn first_success<'a, T>(providers: &'a [Box<dyn RateProvider>], call: impl Fn(&'a dyn RateProvider) -> BoxFuture<'a, Result<Option<T>, ExchangeRateError>>) -> Result<Option<T>, ExchangeRateError>
+{
+    let mut last_error = None;
+    for provider in providers {
+        match call(provider.as_ref()).await {
+            Ok(Some(value)) => return Ok(Some(value)),
+            Ok(None) => {},
+            Err(e) => last_error = Some(e),
+        }
+    }
+    match last_error {
+        Some(e) => Err(e),
+        None => Ok(None),
+    }
+}
+
+/// Sorts `values` and returns their median, averaging the two middle values of an even count.
+fn median(values: &mut [Decimal]) -> Option<Decimal>
+{
+    values.sort();
+    let middle = values.len() / 2;
+    match values.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(values[middle]),
+        _ => Some((values[middle - 1] + values[middle]) / Decimal::TWO),
+    }
+}
+
+fn earliest(a: Option<i64>, b: Option<i64>) -> Option<i64>
+{
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    /// Serves fixed USD rates, or fails with a server error when `rates` is `None`.
+    struct FixedProvider
+    {
+        name: &'static str,
+        rates: Option<Vec<(&'static str, &'static str)>>,
+        time_last_update_unix: i64,
+    }
+
+    #[async_trait]
+    impl RateProvider for FixedProvider
+    {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError> {
+            let rates = self.rates.as_ref().ok_or(ExchangeRateError::Server(reqwest::StatusCode::BAD_GATEWAY))?;
+            Ok(Some(LatestRates {
+                base,
+                conversion_rates: rates.iter().map(|(code, rate)| (code.to_string(), Decimal::from_str(rate).unwrap())).collect(),
+                time_last_update_unix: Some(self.time_last_update_unix),
+                time_next_update_unix: Some(self.time_last_update_unix + 86400),
+            }))
+        }
+
+        async fn supported_codes(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError> {
+            Ok(None)
+        }
+    }
+
+    fn fixed(name: &'static str, pln_rate: &'static str) -> Box<dyn RateProvider> {
+        Box::new(FixedProvider { name, rates: Some(vec![("USD", "1"), ("PLN", pln_rate)]), time_last_update_unix: 1711065600 })
+    }
+
+    fn failing(name: &'static str) -> Box<dyn RateProvider> {
+        Box::new(FixedProvider { name, rates: None, time_last_update_unix: 1711065600 })
+    }
+
+    fn pln() -> CurrencyCode {
+        CurrencyCode::from_str("PLN").unwrap()
+    }
+
+    #[tokio::test]
+    async fn failover_skips_failing_providers() {
+        let provider = FailoverProvider::new(vec![failing("down"), fixed("second", "4.0"), fixed("third", "5.0")]);
+
+        let pair_conversion = provider.pair_conversion(CurrencyCode::USD, pln(), Decimal::from(10)).await.unwrap().unwrap();
+        assert_eq!(pair_conversion.result.amount, Decimal::from(40));
+
+        let (provider_name, _) = provider.served_latest_rates(CurrencyCode::USD).await.unwrap().unwrap();
+        assert_eq!(provider_name, "second");
+
+        let provider = FailoverProvider::new(vec![failing("down"), failing("also down")]);
+        assert!(matches!(provider.latest_rates(CurrencyCode::USD).await, Err(ExchangeRateError::Server(_))));
+    }
+
+    #[tokio::test]
+    async fn consensus_reports_median_and_spread() {
+        let provider = ConsensusProvider::new(vec![fixed("a", "4.00"), fixed("b", "4.02"), fixed("c", "4.01"), failing("down")]);
+
+        let consensus = provider.consensus_rate(CurrencyCode::USD, pln()).await.unwrap().unwrap();
+        assert_eq!(consensus.median, Decimal::from_str("4.01").unwrap());
+        assert_eq!(consensus.spread, Decimal::from_str("0.02").unwrap());
+        assert_eq!(consensus.quotes.len(), 3);
+        assert_eq!(consensus.failures.len(), 1);
+        assert_eq!(consensus.failures[0].0, "down");
+        assert!(!consensus.divergent);
+
+        let latest_rates = provider.latest_rates(CurrencyCode::USD).await.unwrap().unwrap();
+        assert_eq!(latest_rates.conversion_rates["PLN"], Decimal::from_str("4.01").unwrap());
+    }
+
+    #[tokio::test]
+    async fn consensus_conversion_keeps_the_oldest_update_and_checks_overflow() {
+        let older = Box::new(FixedProvider { name: "older", rates: Some(vec![("USD", "1"), ("PLN", "4.0")]), time_last_update_unix: 1710979200 });
+        let provider = ConsensusProvider::new(vec![fixed("a", "4.02"), older]);
+
+        let pair_conversion = provider.pair_conversion(CurrencyCode::USD, pln(), Decimal::from(10)).await.unwrap().unwrap();
+        assert_eq!(pair_conversion.result.amount, Decimal::from_str("40.1").unwrap());
+        assert_eq!(pair_conversion.time_last_update_unix, Some(1710979200));
+        assert_eq!(pair_conversion.time_next_update_unix, Some(1711065600));
+
+        assert!(matches!(provider.pair_conversion(CurrencyCode::USD, pln(), Decimal::MAX).await, Err(ExchangeRateError::AmountOverflow)));
+    }
+
+    #[tokio::test]
+    async fn consensus_flags_divergence_above_threshold() {
+        let providers = || vec![fixed("a", "4.0"), fixed("b", "4.2")];
+
+        let consensus = ConsensusProvider::new(providers()).consensus_rate(CurrencyCode::USD, pln()).await.unwrap().unwrap();
+        assert_eq!(consensus.median, Decimal::from_str("4.1").unwrap());
+        assert!(consensus.divergent);
+
+        let consensus = ConsensusProvider::new(providers())
+            .with_divergence_threshold(Decimal::from_str("0.05").unwrap())
+            .consensus_rate(CurrencyCode::USD, pln()).await.unwrap().unwrap();
+        assert!(!consensus.divergent);
+    }
+
+    #[tokio::test]
+    async fn consensus_without_answers_returns_error() {
+        let provider = ConsensusProvider::new(vec![failing("down")]);
+        assert!(matches!(provider.consensus_rate(CurrencyCode::USD, pln()).await, Err(ExchangeRateError::Server(_))));
+    }
+}
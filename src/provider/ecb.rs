@@ -0,0 +1,133 @@
+use super::{default_http_client, supported_codes_from_rates, RateProvider};
+use crate::currency::CurrencyCode;
+use crate::ecb::{self, EcbRates};
+use crate::error::ExchangeRateError;
+use crate::models::{LatestRates, SupportedCurrency};
+use crate::rate_table::RateTable;
+
+use async_trait::async_trait;
+use reqwest::Client;
+
+/// Euro reference rates from the European Central Bank's daily XML feed.
+/// Other bases are cross rates through EUR; only about 30 currencies are covered.
+#[derive(Clone)]
+pub struct EcbProvider
+{
+    http_client: Client,
+    url: String,
+}
+
+impl EcbProvider
+{
+    pub fn new() -> Result<Self, ExchangeRateError>
+    {
+        Ok(Self::with_client(default_http_client()?, ecb::DAILY_URL))
+    }
+
+    pub fn with_client(http_client: Client, url: &str) -> Self
+    {
+        Self {
+            http_client,
+            url: url.to_string(),
+        }
+    }
+
+    pub async fn fetch_daily(&self) -> Result<EcbRates, ExchangeRateError>
+    {
+        ecb::fetch_daily(&self.http_client, &self.url).await
+    }
+}
+
+#[async_trait]
+impl RateProvider for EcbProvider
+{
+    fn name(&self) -> &'static str
+    {
+        "ecb"
+    }
+
+    async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError>
+    {
+        let euro_rates = self.fetch_daily().await?.to_latest_rates();
+        let rate_table = RateTable::from_latest_rates(&euro_rates).rebase(base).ok_or(ExchangeRateError::UnsupportedCode)?;
+
+        Ok(Some(LatestRates {
+            base,
+            conversion_rates: rate_table.rates().iter()
+                .map(|(code, rate)| (code.to_string(), rate.normalize()))
+                .collect(),
+            // The feed only carries the reference date, not the publication time.
+            time_last_update_unix: euro_rates.time_last_update_unix,
+            time_next_update_unix: None,
+        }))
+    }
+
+    async fn supported_codes(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError>
+    {
+        Ok(self.latest_rates(CurrencyCode::EUR).await?.as_ref().map(supported_codes_from_rates))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use wiremock::{MockServer, Mock, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+
+    const DAILY_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+	<Cube>
+		<Cube time='2024-03-22'>
+			<Cube currency='USD' rate='1.25'/>
+			<Cube currency='PLN' rate='4.5'/>
+		</Cube>
+	</Cube>
+</gesmes:Envelope>"#;
+
+    async fn mock_provider(mock_server: &MockServer, status: u16, body: &str) -> EcbProvider {
+        Mock::given(method("GET"))
+            .and(path("/eurofxref-daily.xml"))
+            .respond_with(ResponseTemplate::new(status).set_body_string(body))
+            .mount(mock_server)
+            .await;
+        EcbProvider::with_client(Client::new(), &format!("{}/eurofxref-daily.xml", mock_server.uri()))
+    }
+
+    #[tokio::test]
+    async fn rebases_euro_rates_and_converts_pairs() {
+        let mock_server = MockServer::start().await;
+        let provider = mock_provider(&mock_server, 200, DAILY_XML).await;
+        let usd = CurrencyCode::USD;
+        let pln = CurrencyCode::from_str("PLN").unwrap();
+
+        let latest_rates = provider.latest_rates(usd).await.unwrap().unwrap();
+        assert_eq!(latest_rates.base, usd);
+        assert_eq!(latest_rates.conversion_rates["USD"], Decimal::ONE);
+        assert_eq!(latest_rates.conversion_rates["EUR"], Decimal::from_str("0.8").unwrap());
+        assert_eq!(latest_rates.conversion_rates["PLN"], Decimal::from_str("3.6").unwrap());
+        assert_eq!(latest_rates.time_last_update_unix, Some(1711065600));
+
+        let pair_conversion = provider.pair_conversion(usd, pln, Decimal::from(10)).await.unwrap().unwrap();
+        assert_eq!(pair_conversion.conversion_rate, Decimal::from_str("3.6").unwrap());
+        assert_eq!(pair_conversion.result.amount, Decimal::from(36));
+
+        let supported_codes = provider.supported_codes().await.unwrap().unwrap();
+        let codes: Vec<&str> = supported_codes.iter().map(|currency| currency.code.as_str()).collect();
+        assert_eq!(codes, ["EUR", "PLN", "USD"]);
+        assert_eq!(supported_codes[1].name, "Zloty");
+    }
+
+    #[tokio::test]
+    async fn reports_uncovered_base_and_server_errors() {
+        let mock_server = MockServer::start().await;
+        let provider = mock_provider(&mock_server, 200, DAILY_XML).await;
+        let gbp = CurrencyCode::from_str("GBP").unwrap();
+        assert!(matches!(provider.latest_rates(gbp).await, Err(ExchangeRateError::UnsupportedCode)));
+
+        let mock_server = MockServer::start().await;
+        let provider = mock_provider(&mock_server, 503, "").await;
+        assert!(matches!(provider.latest_rates(CurrencyCode::EUR).await, Err(ExchangeRateError::Server(_))));
+    }
+}
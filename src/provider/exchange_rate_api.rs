@@ -0,0 +1,123 @@
+use super::{supported_codes_from_rates, RateProvider};
+use crate::client::{ExchangeRateClient, OpenAccessClient};
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::{LatestRates, PairConversion, SupportedCurrency};
+use crate::request_handler::ApiRequest;
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+#[async_trait]
+impl RateProvider for ExchangeRateClient
+{
+    fn name(&self) -> &'static str
+    {
+        "exchangerate-api"
+    }
+
+    async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError>
+    {
+        self.conversion_rates(&base).fetch().await
+    }
+
+    async fn pair_conversion(&self, base: CurrencyCode, target: CurrencyCode, amount: Decimal) -> Result<Option<PairConversion>, ExchangeRateError>
+    {
+        self.standard_request(&base, &target, &amount).fetch().await
+    }
+
+    async fn supported_codes(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError>
+    {
+        ExchangeRateClient::supported_codes(self).fetch().await
+    }
+}
+
+/// The keyless endpoint has neither pair conversions nor a codes endpoint,
+/// so both are derived from the latest USD rates.
+#[async_trait]
+impl RateProvider for OpenAccessClient
+{
+    fn name(&self) -> &'static str
+    {
+        "exchangerate-api"
+    }
+
+    async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError>
+    {
+        self.conversion_rates(&base).fetch().await
+    }
+
+    async fn supported_codes(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError>
+    {
+        Ok(self.latest_rates(CurrencyCode::USD).await?.as_ref().map(supported_codes_from_rates))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use wiremock::{MockServer, Mock, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+
+    #[tokio::test]
+    async fn keyed_client_uses_pair_endpoint() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "base_code": "USD",
+            "target_code": "PLN",
+            "conversion_rate": 4.0,
+            "conversion_result": 40.0
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/pair/USD/PLN/10"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let provider: Box<dyn RateProvider> = Box::new(ExchangeRateClient::builder("111111111111111111111111")
+            .prefix(&mock_server.uri())
+            .build()
+            .unwrap());
+        let pln = CurrencyCode::from_str("PLN").unwrap();
+
+        let pair_conversion = provider.pair_conversion(CurrencyCode::USD, pln, Decimal::from(10)).await.unwrap().unwrap();
+        assert_eq!(pair_conversion.result.amount, Decimal::from(40));
+    }
+
+    #[tokio::test]
+    async fn open_access_derives_pairs_and_codes_from_latest_rates() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "base_code": "USD",
+            "time_last_update_unix": 1711065600,
+            "rates": {"USD": 1, "PLN": 4.0, "EUR": 0.8}
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/latest/USD"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let provider = OpenAccessClient::builder()
+            .prefix(&mock_server.uri())
+            .build()
+            .unwrap();
+
+        let pair_conversion = provider.pair_conversion(CurrencyCode::USD, CurrencyCode::EUR, Decimal::from(10)).await.unwrap().unwrap();
+        assert_eq!(pair_conversion.result.amount, Decimal::from(8));
+        assert_eq!(pair_conversion.time_last_update_unix, Some(1711065600));
+
+        let supported_codes = provider.supported_codes().await.unwrap().unwrap();
+        assert_eq!(supported_codes.len(), 3);
+        assert_eq!(supported_codes[0].code, "EUR");
+    }
+}
@@ -0,0 +1,151 @@
+use super::{default_http_client, RateProvider};
+use crate::currency::CurrencyCode;
+use crate::error::{check_status, ExchangeRateError};
+use crate::models::{LatestRates, SupportedCurrency};
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveTime};
+use reqwest::{Client, StatusCode};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+pub const FRANKFURTER_PREFIX: &str = "https://api.frankfurter.app";
+
+/// Keyless JSON API serving the ECB reference rates under any of the covered bases.
+#[derive(Clone)]
+pub struct FrankfurterProvider
+{
+    http_client: Client,
+    prefix: String,
+}
+
+#[derive(Deserialize)]
+struct FrankfurterRates
+{
+    date: NaiveDate,
+    rates: HashMap<String, Decimal>,
+}
+
+impl FrankfurterProvider
+{
+    pub fn new() -> Result<Self, ExchangeRateError>
+    {
+        Ok(Self::with_client(default_http_client()?, FRANKFURTER_PREFIX))
+    }
+
+    pub fn with_client(http_client: Client, prefix: &str) -> Self
+    {
+        Self {
+            http_client,
+            prefix: prefix.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl RateProvider for FrankfurterProvider
+{
+    fn name(&self) -> &'static str
+    {
+        "frankfurter"
+    }
+
+    async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError>
+    {
+        let response = self.http_client
+            .get(format!("{}/latest", self.prefix))
+            .query(&[("base", base.as_str())])
+            .send()
+            .await?;
+        // Unknown bases are answered with 404 Not Found.
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(ExchangeRateError::UnsupportedCode);
+        }
+        let frankfurter_rates: FrankfurterRates = check_status(response)?.json().await?;
+
+        // The base is left out of the rates, unlike in ExchangeRate-API responses.
+        let mut conversion_rates = frankfurter_rates.rates;
+        conversion_rates.insert(base.to_string(), Decimal::ONE);
+        Ok(Some(LatestRates {
+            base,
+            conversion_rates,
+            time_last_update_unix: Some(frankfurter_rates.date.and_time(NaiveTime::MIN).and_utc().timestamp()),
+            time_next_update_unix: None,
+        }))
+    }
+
+    async fn supported_codes(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError>
+    {
+        let response = self.http_client
+            .get(format!("{}/currencies", self.prefix))
+            .send()
+            .await?;
+        let currencies: HashMap<String, String> = check_status(response)?.json().await?;
+
+        let mut supported_codes: Vec<SupportedCurrency> = currencies.into_iter()
+            .map(|(code, name)| SupportedCurrency { code, name })
+            .collect();
+        supported_codes.sort_by(|a, b| a.code.cmp(&b.code));
+        Ok(Some(supported_codes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use wiremock::{MockServer, Mock, ResponseTemplate};
+    use wiremock::matchers::{method, path, query_param};
+
+    #[tokio::test]
+    async fn fetches_rates_pairs_and_codes() {
+        let mock_server = MockServer::start().await;
+        let body_latest = r#"{"amount": 1.0, "base": "USD", "date": "2024-03-22", "rates": {"EUR": 0.8, "PLN": 4.0}}"#;
+        let body_currencies = r#"{"EUR": "Euro", "USD": "United States Dollar", "PLN": "Polish Złoty"}"#;
+
+        Mock::given(method("GET"))
+            .and(path("/latest"))
+            .and(query_param("base", "USD"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_latest))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/currencies"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_currencies))
+            .mount(&mock_server)
+            .await;
+
+        let provider = FrankfurterProvider::with_client(Client::new(), &mock_server.uri());
+        let usd = CurrencyCode::USD;
+
+        let latest_rates = provider.latest_rates(usd).await.unwrap().unwrap();
+        assert_eq!(latest_rates.conversion_rates.len(), 3);
+        assert_eq!(latest_rates.conversion_rates["USD"], Decimal::ONE);
+        assert_eq!(latest_rates.time_last_update_unix, Some(1711065600));
+
+        let pair_conversion = provider.pair_conversion(usd, CurrencyCode::EUR, Decimal::from(10)).await.unwrap().unwrap();
+        assert_eq!(pair_conversion.conversion_rate, Decimal::from_str("0.8").unwrap());
+        assert_eq!(pair_conversion.result.amount, Decimal::from(8));
+
+        let supported_codes = provider.supported_codes().await.unwrap().unwrap();
+        assert_eq!(supported_codes.len(), 3);
+        assert_eq!(supported_codes[0].code, "EUR");
+        assert_eq!(supported_codes[1].name, "Polish Złoty");
+    }
+
+    #[tokio::test]
+    async fn unknown_base_is_unsupported() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/latest"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(r#"{"message": "not found"}"#))
+            .mount(&mock_server)
+            .await;
+
+        let provider = FrankfurterProvider::with_client(Client::new(), &mock_server.uri());
+        let xau = CurrencyCode::from_str("XAU").unwrap();
+        assert!(matches!(provider.latest_rates(xau).await, Err(ExchangeRateError::UnsupportedCode)));
+    }
+}
@@ -0,0 +1,202 @@
+mod composite;
+mod ecb;
+mod exchange_rate_api;
+mod frankfurter;
+mod offline;
+
+pub use composite::{Consensus, ConsensusProvider, FailoverProvider, ProviderQuote, DEFAULT_DIVERGENCE_THRESHOLD};
+pub use ecb::EcbProvider;
+pub use frankfurter::{FrankfurterProvider, FRANKFURTER_PREFIX};
+pub use offline::OfflineProvider;
+
+use crate::client::{ExchangeRateClient, OpenAccessClient, DEFAULT_CONNECT_TIMEOUT, DEFAULT_TIMEOUT};
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::{LatestRates, PairConversion, SupportedCurrency};
+use crate::rate_table::RateTable;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use std::fmt;
+use std::str::FromStr;
+
+/// A source of exchange rates. Each backend maps its own API onto the crate's models,
+/// so callers can switch between them at runtime through `Box<dyn RateProvider>`.
+#[async_trait]
+pub trait RateProvider: Send + Sync
+{
+    fn name(&self) -> &'static str;
+
+    async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError>;
+
+    /// Converts `amount` of `base` into `target`; by default computed locally from `latest_rates`.
+    async fn pair_conversion(&self, base: CurrencyCode, target: CurrencyCode, amount: Decimal) -> Result<Option<PairConversion>, ExchangeRateError>
+    {
+        let latest_rates = match self.latest_rates(base).await? {
+            Some(latest_rates) => latest_rates,
+            None => return Ok(None),
+        };
+        let rate_table = RateTable::from_latest_rates(&latest_rates);
+        pair_conversion_from(&latest_rates, &rate_table, base, target, amount).map(Some)
+    }
+
+    /// Converts `amount` of `base` into each of `targets`, or into every other currency
+    /// of the table when `None`, from a single `latest_rates` call.
+    async fn conversions(&self, base: CurrencyCode, targets: Option<&[CurrencyCode]>, amount: Decimal) -> Result<Option<Vec<PairConversion>>, ExchangeRateError>
+    {
+        match self.latest_rates(base).await? {
+            Some(latest_rates) => conversions_from(&latest_rates, targets, amount).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn supported_codes(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError>;
+}
+
+/// Selects a backend by name, e.g. from a command line flag or environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProviderKind
+{
+    #[default]
+    ExchangeRateApi,
+    Ecb,
+    Frankfurter,
+}
+
+impl ProviderKind
+{
+    pub const ALL: [ProviderKind; 3] = [ProviderKind::ExchangeRateApi, ProviderKind::Ecb, ProviderKind::Frankfurter];
+
+    pub fn as_str(&self) -> &'static str
+    {
+        match self {
+            ProviderKind::ExchangeRateApi => "exchangerate-api",
+            ProviderKind::Ecb => "ecb",
+            ProviderKind::Frankfurter => "frankfurter",
+        }
+    }
+
+    /// Builds the backend with its default endpoint. ExchangeRate-API falls back to the
+    /// keyless open access endpoint without `api_key`; the other backends need no key.
+    pub fn build(&self, api_key: Option<&str>) -> Result<Box<dyn RateProvider>, ExchangeRateError>
+    {
+        Ok(match (self, api_key) {
+            (ProviderKind::ExchangeRateApi, Some(api_key)) => Box::new(ExchangeRateClient::new(api_key)?),
+            (ProviderKind::ExchangeRateApi, None) => Box::new(OpenAccessClient::new()?),
+            (ProviderKind::Ecb, _) => Box::new(EcbProvider::new()?),
+            (ProviderKind::Frankfurter, _) => Box::new(FrankfurterProvider::new()?),
+        })
+    }
+}
+
+impl FromStr for ProviderKind
+{
+    type Err = ExchangeRateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let name = s.trim().to_ascii_lowercase();
+        ProviderKind::ALL.into_iter()
+            .find(|kind| kind.as_str() == name)
+            .ok_or_else(|| ExchangeRateError::UnknownProvider(s.to_string()))
+    }
+}
+
+impl fmt::Display for ProviderKind
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        f.write_str(self.as_str())
+    }
+}
+
+fn default_http_client() -> Result<Client, ExchangeRateError>
+{
+    Ok(Client::builder()
+        .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+        .timeout(DEFAULT_TIMEOUT)
+        .build()?)
+}
+
+/// Converts `amount` of the base of `latest_rates` into each of `targets`, or into every
+/// other currency of the table when `None`, for callers that already hold the table.
+pub fn conversions_from(latest_rates: &LatestRates, targets: Option<&[CurrencyCode]>, amount: Decimal)
+    -> Result<Vec<PairConversion>, ExchangeRateError>
+{
+    let base = latest_rates.base;
+    let rate_table = RateTable::from_latest_rates(latest_rates);
+    let targets = match targets {
+        Some(targets) => targets.to_vec(),
+        None => {
+            let mut targets: Vec<CurrencyCode> = rate_table.rates().keys().copied().filter(|code| *code != base).collect();
+            targets.sort();
+            targets
+        },
+    };
+    targets.into_iter()
+        .map(|target| pair_conversion_from(latest_rates, &rate_table, base, target, amount))
+        .collect()
+}
+
+/// Converts through `rate_table`, built from `latest_rates`, whose update times the result
+/// carries. Fails with `UnsupportedCode` without a rate for the pair and `AmountOverflow`
+/// when the converted amount does not fit.
+fn pair_conversion_from(latest_rates: &LatestRates, rate_table: &RateTable, base: CurrencyCode, target: CurrencyCode, amount: Decimal)
+    -> Result<PairConversion, ExchangeRateError>
+{
+    let conversion_rate = rate_table.rate(base, target).ok_or(ExchangeRateError::UnsupportedCode)?;
+    let result = rate_table.convert(amount, base, target).ok_or(ExchangeRateError::AmountOverflow)?;
+
+    Ok(PairConversion {
+        base,
+        target,
+        conversion_rate,
+        result,
+        time_last_update_unix: latest_rates.time_last_update_unix,
+        time_next_update_unix: latest_rates.time_next_update_unix,
+    })
+}
+
+/// Names the codes of a rate map from the embedded currency table, for backends without a codes endpoint.
+fn supported_codes_from_rates(latest_rates: &LatestRates) -> Vec<SupportedCurrency>
+{
+    let mut supported_codes: Vec<SupportedCurrency> = latest_rates.conversion_rates.keys()
+        .filter_map(|code| code.parse::<CurrencyCode>().ok())
+        .map(|code| SupportedCurrency { code: code.to_string(), name: code.name().to_string() })
+        .collect();
+    supported_codes.sort_by(|a, b| a.code.cmp(&b.code));
+    supported_codes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_kind_round_trips_through_its_name() {
+        for kind in ProviderKind::ALL {
+            assert_eq!(kind.to_string().parse::<ProviderKind>().unwrap(), kind);
+        }
+        assert_eq!(" ECB ".parse::<ProviderKind>().unwrap(), ProviderKind::Ecb);
+        assert!(matches!("fixer".parse::<ProviderKind>(), Err(ExchangeRateError::UnknownProvider(_))));
+    }
+
+    #[test]
+    fn tells_a_missing_rate_from_an_overflow() {
+        let latest_rates = LatestRates {
+            base: CurrencyCode::USD,
+            conversion_rates: std::collections::HashMap::from([("USD".to_string(), Decimal::ONE), ("PLN".to_string(), Decimal::from(4))]),
+            time_last_update_unix: Some(1711065601),
+            time_next_update_unix: None,
+        };
+        let pln = CurrencyCode::from_str("PLN").unwrap();
+        let jpy = CurrencyCode::from_str("JPY").unwrap();
+
+        let conversions = conversions_from(&latest_rates, Some(&[pln]), Decimal::from(10)).unwrap();
+        assert_eq!(conversions[0].conversion_rate, Decimal::from(4));
+        assert_eq!(conversions[0].time_last_update_unix, Some(1711065601));
+        assert!(matches!(conversions_from(&latest_rates, Some(&[jpy]), Decimal::from(10)), Err(ExchangeRateError::UnsupportedCode)));
+        assert!(matches!(conversions_from(&latest_rates, Some(&[pln]), Decimal::MAX), Err(ExchangeRateError::AmountOverflow)));
+    }
+}
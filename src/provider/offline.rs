@@ -0,0 +1,212 @@
+use super::{supported_codes_from_rates, RateProvider};
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::{ApiResponse, LatestRates, SupportedCurrency};
+use crate::rate_table::RateTable;
+use crate::snapshot::RatesSnapshot;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::Path;
+
+/// Serves rates from a saved `ConversionRates` response or `RatesSnapshot` without any network access.
+/// Bases missing from the file are cross rates through the first table that lists them.
+#[derive(Debug, Clone)]
+pub struct OfflineProvider
+{
+    rates: Vec<LatestRates>,
+}
+
+impl OfflineProvider
+{
+    /// Reads either a versioned `RatesSnapshot` or a single `ApiResponse`.
+    pub fn load(path: &Path) -> Result<Self, ExchangeRateError>
+    {
+        let json = fs::read_to_string(path)?;
+        let value: serde_json::Value = serde_json::from_str(&json)
+            .map_err(|e| ExchangeRateError::InvalidResponse(format!("{}: {}", path.display(), e)))?;
+        if value.get("version").is_some() {
+            return Ok(Self::from_snapshot(RatesSnapshot::from_json(&json)?));
+        }
+
+        let api_response: ApiResponse = serde_json::from_value(value)
+            .map_err(|e| ExchangeRateError::InvalidResponse(format!("{}: {}", path.display(), e)))?;
+        Self::from_api_response(api_response)
+    }
+
+    pub fn from_api_response(api_response: ApiResponse) -> Result<Self, ExchangeRateError>
+    {
+        let base = api_response.base_code.as_deref()
+            .ok_or_else(|| ExchangeRateError::InvalidResponse("snapshot has no base_code".to_string()))?
+            .parse()?;
+        let conversion_rates = api_response.conversion_rates
+            .ok_or_else(|| ExchangeRateError::InvalidResponse("snapshot has no conversion_rates".to_string()))?;
+
+        Ok(Self::from_latest_rates(vec![LatestRates {
+            base,
+            conversion_rates,
+            time_last_update_unix: api_response.time_last_update_unix,
+            time_next_update_unix: api_response.time_next_update_unix,
+        }]))
+    }
+
+    pub fn from_snapshot(snapshot: RatesSnapshot) -> Self
+    {
+        Self::from_latest_rates(snapshot.rates.into_iter().map(LatestRates::from).collect())
+    }
+
+    pub fn from_latest_rates(rates: Vec<LatestRates>) -> Self
+    {
+        Self {
+            rates,
+        }
+    }
+
+    /// Time of the oldest table in the file.
+    pub fn last_update(&self) -> Option<DateTime<Utc>>
+    {
+        let time_last_update_unix = self.rates.iter().filter_map(|latest_rates| latest_rates.time_last_update_unix).min()?;
+        DateTime::from_timestamp(time_last_update_unix, 0)
+    }
+
+    /// Earliest time at which the API published newer rates than one of the tables in the file.
+    pub fn next_update(&self) -> Option<DateTime<Utc>>
+    {
+        let time_next_update_unix = self.rates.iter().filter_map(|latest_rates| latest_rates.time_next_update_unix).min()?;
+        DateTime::from_timestamp(time_next_update_unix, 0)
+    }
+
+    /// Whether the API had already published newer rates at `now`.
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool
+    {
+        self.next_update().is_some_and(|next_update| next_update <= now)
+    }
+}
+
+#[async_trait]
+impl RateProvider for OfflineProvider
+{
+    fn name(&self) -> &'static str
+    {
+        "offline"
+    }
+
+    async fn latest_rates(&self, base: CurrencyCode) -> Result<Option<LatestRates>, ExchangeRateError>
+    {
+        if let Some(latest_rates) = self.rates.iter().find(|latest_rates| latest_rates.base == base) {
+            return Ok(Some(latest_rates.clone()));
+        }
+        let (latest_rates, rate_table) = self.rates.iter()
+            .find_map(|latest_rates| Some((latest_rates, RateTable::from_latest_rates(latest_rates).rebase(base)?)))
+            .ok_or(ExchangeRateError::UnsupportedCode)?;
+
+        Ok(Some(LatestRates {
+            base,
+            conversion_rates: rate_table.rates().iter()
+                .map(|(code, rate)| (code.to_string(), rate.normalize()))
+                .collect(),
+            ..latest_rates.clone()
+        }))
+    }
+
+    async fn supported_codes(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError>
+    {
+        let mut supported_codes: Vec<SupportedCurrency> = Vec::new();
+        for latest_rates in &self.rates {
+            for supported_currency in supported_codes_from_rates(latest_rates) {
+                if !supported_codes.iter().any(|listed| listed.code == supported_currency.code) {
+                    supported_codes.push(supported_currency);
+                }
+            }
+        }
+        supported_codes.sort_by(|a, b| a.code.cmp(&b.code));
+        Ok(Some(supported_codes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    const SNAPSHOT: &str = r#"
+    {
+        "result": "success",
+        "time_last_update_unix": 1711065601,
+        "time_next_update_unix": 1711152001,
+        "base_code": "USD",
+        "conversion_rates": {"USD": 1, "PLN": 4.0, "EUR": 0.8}
+    }"#;
+
+    fn snapshot_provider() -> OfflineProvider {
+        OfflineProvider::from_api_response(serde_json::from_str(SNAPSHOT).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn converts_into_many_targets_from_one_table() {
+        let provider = snapshot_provider();
+        let pln = CurrencyCode::from_str("PLN").unwrap();
+
+        let conversions = provider.conversions(CurrencyCode::EUR, Some(&[pln, CurrencyCode::USD]), Decimal::from(10)).await.unwrap().unwrap();
+        let results: Vec<_> = conversions.iter().map(|conversion| (conversion.target, conversion.result.amount)).collect();
+        assert_eq!(results, [(pln, Decimal::from(50)), (CurrencyCode::USD, Decimal::from_str("12.5").unwrap())]);
+
+        let targets: Vec<_> = provider.conversions(pln, None, Decimal::ONE).await.unwrap().unwrap().iter().map(|conversion| conversion.target).collect();
+        assert_eq!(targets, [CurrencyCode::EUR, CurrencyCode::USD]);
+
+        let jpy = CurrencyCode::from_str("JPY").unwrap();
+        assert!(matches!(provider.conversions(pln, Some(&[jpy]), Decimal::ONE).await, Err(ExchangeRateError::UnsupportedCode)));
+    }
+
+    #[tokio::test]
+    async fn serves_rates_conversions_and_codes_from_snapshot() {
+        let provider = snapshot_provider();
+        let pln = CurrencyCode::from_str("PLN").unwrap();
+
+        let latest_rates = provider.latest_rates(CurrencyCode::EUR).await.unwrap().unwrap();
+        assert_eq!(latest_rates.conversion_rates["PLN"], Decimal::from(5));
+        assert_eq!(latest_rates.time_last_update_unix, Some(1711065601));
+
+        let pair_conversion = provider.pair_conversion(pln, CurrencyCode::USD, Decimal::from(100)).await.unwrap().unwrap();
+        assert_eq!(pair_conversion.result.amount, Decimal::from(25));
+
+        assert_eq!(provider.supported_codes().await.unwrap().unwrap().len(), 3);
+        assert!(matches!(provider.latest_rates(CurrencyCode::from_str("GBP").unwrap()).await, Err(ExchangeRateError::UnsupportedCode)));
+    }
+
+    #[test]
+    fn detects_expired_snapshot() {
+        let provider = snapshot_provider();
+
+        assert!(!provider.is_stale(DateTime::from_timestamp(1711152000, 0).unwrap()));
+        assert!(provider.is_stale(DateTime::from_timestamp(1711152001, 0).unwrap()));
+    }
+
+    #[test]
+    fn rejects_snapshots_without_rates() {
+        let path = std::env::temp_dir().join(format!("exchange_rate_api_offline_{}.json", std::process::id()));
+        fs::write(&path, r#"{"result": "success", "base_code": "USD"}"#).unwrap();
+
+        assert!(matches!(OfflineProvider::load(&path), Err(ExchangeRateError::InvalidResponse(_))));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(OfflineProvider::load(&path), Err(ExchangeRateError::Io(_))));
+    }
+
+    #[tokio::test]
+    async fn loads_versioned_snapshots() {
+        let path = std::env::temp_dir().join(format!("exchange_rate_api_offline_snapshot_{}.json", std::process::id()));
+        let usd_rates = snapshot_provider().latest_rates(CurrencyCode::USD).await.unwrap().unwrap();
+        let mut gbp_rates = usd_rates.clone();
+        gbp_rates.base = CurrencyCode::from_str("GBP").unwrap();
+        gbp_rates.time_next_update_unix = Some(1711000000);
+        RatesSnapshot::new(vec![usd_rates, gbp_rates]).write(&path).unwrap();
+
+        let provider = OfflineProvider::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(provider.next_update(), DateTime::from_timestamp(1711000000, 0));
+        assert_eq!(provider.latest_rates(CurrencyCode::from_str("GBP").unwrap()).await.unwrap().unwrap().conversion_rates.len(), 3);
+        assert_eq!(provider.latest_rates(CurrencyCode::EUR).await.unwrap().unwrap().conversion_rates["PLN"], Decimal::from(5));
+    }
+}
@@ -0,0 +1,369 @@
+use crate::error::ExchangeRateError;
+use crate::file;
+
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// What to do when no token is available in the bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LimitMode
+{
+    Delay,
+    Refuse,
+}
+
+/// Classic token bucket: holds up to `capacity` tokens and regains `refill_per_second` of them every second.
+/// With no refill the bucket is a fixed budget that is never replenished.
+#[derive(Debug)]
+pub struct TokenBucket
+{
+    capacity: f64,
+    refill_per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket
+{
+    /// A negative or NaN `refill_per_second` is treated as no refill.
+    pub fn new(capacity: u32, refill_per_second: f64) -> Self
+    {
+        Self {
+            capacity: capacity as f64,
+            refill_per_second: refill_per_second.max(0.0),
+            tokens: capacity as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self)
+    {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Takes one token, or returns how long to wait until one becomes available;
+    /// `None` if the bucket never refills.
+    fn try_take(&mut self) -> Result<(), Option<Duration>>
+    {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        }
+        else if self.refill_per_second > 0.0 {
+            Err(Duration::try_from_secs_f64((1.0 - self.tokens) / self.refill_per_second).ok())
+        }
+        else {
+            Err(None)
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct QuotaState
+{
+    used: u64,
+    period_start: String,
+}
+
+/// Counts requests sent in the current plan period, optionally persisted to a file
+/// so the count survives between runs of the program.
+#[derive(Debug)]
+pub struct QuotaCounter
+{
+    limit: u64,
+    used: u64,
+    refresh_day: u32,
+    period_start: NaiveDate,
+    path: Option<PathBuf>,
+}
+
+impl QuotaCounter
+{
+    /// `refresh_day` is the day of month on which the plan quota resets (clamped to 1-28).
+    pub fn new(limit: u64, refresh_day: u32) -> Self
+    {
+        let refresh_day = refresh_day.clamp(1, 28);
+        Self {
+            limit,
+            used: 0,
+            refresh_day,
+            period_start: period_start(Local::now().date_naive(), refresh_day),
+            path: None,
+        }
+    }
+
+    /// Loads the counter from `path` if it exists; every change is written back to it.
+    pub fn persisted(limit: u64, refresh_day: u32, path: PathBuf) -> Result<Self, std::io::Error>
+    {
+        let mut quota_counter = Self::new(limit, refresh_day);
+        if path.exists() {
+            let state: QuotaState = serde_json::from_str(&fs::read_to_string(&path)?)?;
+            if let Ok(period_start) = state.period_start.parse::<NaiveDate>() {
+                if period_start == quota_counter.period_start {
+                    quota_counter.used = state.used;
+                }
+            }
+        }
+        quota_counter.path = Some(path);
+        Ok(quota_counter)
+    }
+
+    pub fn remaining(&self) -> u64
+    {
+        self.limit.saturating_sub(self.used)
+    }
+
+    fn roll_period(&mut self, today: NaiveDate)
+    {
+        let current_period_start = period_start(today, self.refresh_day);
+        if current_period_start != self.period_start {
+            self.period_start = current_period_start;
+            self.used = 0;
+        }
+    }
+
+    fn record(&mut self) -> Result<(), ExchangeRateError>
+    {
+        self.roll_period(Local::now().date_naive());
+        if self.remaining() == 0 {
+            return Err(ExchangeRateError::QuotaReached);
+        }
+        self.used += 1;
+        self.save();
+        Ok(())
+    }
+
+    fn exhaust(&mut self)
+    {
+        self.used = self.limit;
+        self.save();
+    }
+
+    fn save(&self)
+    {
+        if let Some(path) = &self.path {
+            let state = QuotaState {
+                used: self.used,
+                period_start: self.period_start.to_string(),
+            };
+            // A counter that cannot be saved only loses accuracy across runs, so failures are ignored.
+            if let Ok(json) = serde_json::to_string(&state) {
+                let _ = file::write_atomically(path, &json);
+            }
+        }
+    }
+}
+
+fn period_start(today: NaiveDate, refresh_day: u32) -> NaiveDate
+{
+    if today.day() >= refresh_day {
+        today.with_day(refresh_day).unwrap()
+    }
+    else {
+        let (year, month) = if today.month() == 1 { (today.year() - 1, 12) } else { (today.year(), today.month() - 1) };
+        NaiveDate::from_ymd_opt(year, month, refresh_day).unwrap()
+    }
+}
+
+#[derive(Debug)]
+struct LimiterState
+{
+    token_bucket: Option<TokenBucket>,
+    quota_counter: Option<QuotaCounter>,
+}
+
+/// Shared between all requests of a client; checked by `ApiRequest::send` before every attempt.
+#[derive(Debug, Clone)]
+pub struct RateLimiter
+{
+    state: Arc<Mutex<LimiterState>>,
+    mode: LimitMode,
+}
+
+impl RateLimiter
+{
+    pub fn new(mode: LimitMode) -> Self
+    {
+        Self {
+            state: Arc::new(Mutex::new(LimiterState {
+                token_bucket: None,
+                quota_counter: None,
+            })),
+            mode,
+        }
+    }
+
+    pub fn with_token_bucket(self, token_bucket: TokenBucket) -> Self
+    {
+        self.state.lock().unwrap().token_bucket = Some(token_bucket);
+        self
+    }
+
+    pub fn with_quota_counter(self, quota_counter: QuotaCounter) -> Self
+    {
+        self.state.lock().unwrap().quota_counter = Some(quota_counter);
+        self
+    }
+
+    /// Requests left in the current plan period, if a quota counter is configured.
+    pub fn remaining_quota(&self) -> Option<u64>
+    {
+        let mut state = self.state.lock().unwrap();
+        state.quota_counter.as_mut().map(|quota_counter| {
+            quota_counter.roll_period(Local::now().date_naive());
+            quota_counter.remaining()
+        })
+    }
+
+    /// Waits for (or, in `LimitMode::Refuse`, fails without) a free token, then counts the request against the quota.
+    /// A bucket that never refills is refused in either mode rather than waited on forever.
+    pub async fn acquire(&self) -> Result<(), ExchangeRateError>
+    {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                if let Some(quota_counter) = state.quota_counter.as_mut() {
+                    quota_counter.roll_period(Local::now().date_naive());
+                    if quota_counter.remaining() == 0 {
+                        return Err(ExchangeRateError::QuotaReached);
+                    }
+                }
+                let wait = match state.token_bucket.as_mut() {
+                    Some(token_bucket) => token_bucket.try_take().err(),
+                    None => None,
+                };
+                if wait.is_none() {
+                    if let Some(quota_counter) = state.quota_counter.as_mut() {
+                        quota_counter.record()?;
+                    }
+                }
+                wait
+            };
+
+            match wait {
+                None => return Ok(()),
+                Some(None) => return Err(ExchangeRateError::RateLimited),
+                Some(_) if self.mode == LimitMode::Refuse => return Err(ExchangeRateError::RateLimited),
+                Some(Some(wait)) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    /// Called when the API itself answers `quota-reached`, so later calls are refused locally.
+    pub fn mark_quota_reached(&self)
+    {
+        if let Some(quota_counter) = self.state.lock().unwrap().quota_counter.as_mut() {
+            quota_counter.exhaust();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("exchange_rate_api_{}_{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn period_start_follows_refresh_day() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(period_start(date(2024, 3, 20), 15), date(2024, 3, 15));
+        assert_eq!(period_start(date(2024, 3, 10), 15), date(2024, 2, 15));
+        assert_eq!(period_start(date(2024, 1, 10), 15), date(2023, 12, 15));
+    }
+
+    #[test]
+    fn quota_counter_resets_in_new_period() {
+        let mut quota_counter = QuotaCounter::new(10, 1);
+        quota_counter.record().unwrap();
+        assert_eq!(quota_counter.remaining(), 9);
+
+        let next_period = quota_counter.period_start + chrono::Months::new(1);
+        quota_counter.roll_period(next_period);
+        assert_eq!(quota_counter.remaining(), 10);
+    }
+
+    #[test]
+    fn quota_counter_is_persisted_across_instances() {
+        let path = temp_path("quota_persisted");
+
+        let mut quota_counter = QuotaCounter::persisted(5, 1, path.clone()).unwrap();
+        quota_counter.record().unwrap();
+        quota_counter.record().unwrap();
+
+        let quota_counter = QuotaCounter::persisted(5, 1, path.clone()).unwrap();
+        assert_eq!(quota_counter.remaining(), 3);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn quota_counter_leaves_sibling_tmp_files_alone() {
+        let path = temp_path("quota_sibling");
+        let sibling = path.with_extension("tmp");
+        fs::write(&sibling, "keep").unwrap();
+
+        let mut quota_counter = QuotaCounter::persisted(5, 1, path.clone()).unwrap();
+        quota_counter.record().unwrap();
+        assert_eq!(fs::read_to_string(&sibling).unwrap(), "keep");
+        assert_eq!(QuotaCounter::persisted(5, 1, path.clone()).unwrap().remaining(), 4);
+
+        fs::remove_file(sibling).unwrap();
+        fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn refuse_mode_rejects_when_bucket_is_empty() {
+        let rate_limiter = RateLimiter::new(LimitMode::Refuse)
+            .with_token_bucket(TokenBucket::new(2, 0.0));
+
+        assert!(rate_limiter.acquire().await.is_ok());
+        assert!(rate_limiter.acquire().await.is_ok());
+        assert!(matches!(rate_limiter.acquire().await, Err(ExchangeRateError::RateLimited)));
+    }
+
+    #[tokio::test]
+    async fn delay_mode_waits_for_refill() {
+        let rate_limiter = RateLimiter::new(LimitMode::Delay)
+            .with_token_bucket(TokenBucket::new(1, 20.0));
+
+        let started = Instant::now();
+        rate_limiter.acquire().await.unwrap();
+        rate_limiter.acquire().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn delay_mode_refuses_a_bucket_that_never_refills() {
+        for refill_per_second in [0.0, -1.0, f64::NAN] {
+            let rate_limiter = RateLimiter::new(LimitMode::Delay)
+                .with_token_bucket(TokenBucket::new(1, refill_per_second));
+
+            rate_limiter.acquire().await.unwrap();
+            assert!(matches!(rate_limiter.acquire().await, Err(ExchangeRateError::RateLimited)));
+        }
+    }
+
+    #[tokio::test]
+    async fn exhausted_quota_is_refused() {
+        let rate_limiter = RateLimiter::new(LimitMode::Delay)
+            .with_quota_counter(QuotaCounter::new(1, 1));
+
+        rate_limiter.acquire().await.unwrap();
+        assert_eq!(rate_limiter.remaining_quota(), Some(0));
+        assert!(matches!(rate_limiter.acquire().await, Err(ExchangeRateError::QuotaReached)));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::currency::CurrencyCode;
+use crate::models::LatestRates;
+use crate::money::Money;
+
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// Latest rates against a single base currency, as returned by `ConversionRates`.
+/// Converts between any two currencies of the table without further API calls.
+#[derive(Debug, Clone)]
+pub struct RateTable
+{
+    base: CurrencyCode,
+    rates: HashMap<CurrencyCode, Decimal>,
+}
+
+impl RateTable
+{
+    pub fn new(base: CurrencyCode, rates: HashMap<CurrencyCode, Decimal>) -> Self
+    {
+        Self {
+            base,
+            rates,
+        }
+    }
+
+    /// Builds the table from the result of `ConversionRates::fetch`,
+    /// skipping codes missing from the currency table.
+    pub fn from_latest_rates(latest_rates: &LatestRates) -> Self
+    {
+        let rates = latest_rates.conversion_rates.iter()
+            .filter_map(|(code, rate)| Some((code.parse().ok()?, *rate)))
+            .collect();
+        Self::new(latest_rates.base, rates)
+    }
+
+    pub fn base(&self) -> CurrencyCode
+    {
+        self.base
+    }
+
+    pub fn rates(&self) -> &HashMap<CurrencyCode, Decimal>
+    {
+        &self.rates
+    }
+
+    /// Units of the base currency are worth 1; any other code must be listed in the table.
+    fn base_rate(&self, code: CurrencyCode) -> Option<Decimal>
+    {
+        match self.rates.get(&code) {
+            Some(rate) if !rate.is_zero() => Some(*rate),
+            Some(_) => None,
+            None if code == self.base => Some(Decimal::ONE),
+            None => None,
+        }
+    }
+
+    /// Price of one unit of `from` expressed in `to`, via the base currency.
+    pub fn rate(&self, from: CurrencyCode, to: CurrencyCode) -> Option<Decimal>
+    {
+        self.base_rate(to)?.checked_div(self.base_rate(from)?)
+    }
+
+    /// Expresses every rate against `base` instead, or `None` if `base` is missing from the table.
+    pub fn rebase(&self, base: CurrencyCode) -> Option<RateTable>
+    {
+        let base_rate = self.base_rate(base)?;
+        let mut rates: HashMap<CurrencyCode, Decimal> = self.rates.iter()
+            .filter_map(|(code, rate)| Some((*code, rate.checked_div(base_rate)?)))
+            .collect();
+        rates.entry(self.base).or_insert(Decimal::ONE.checked_div(base_rate)?);
+        rates.insert(base, Decimal::ONE);
+        Some(Self::new(base, rates))
+    }
+
+    /// Converts `amount` of `from` into `to`, or `None` if either code is missing from the table.
+    pub fn convert(&self, amount: Decimal, from: CurrencyCode, to: CurrencyCode) -> Option<Money>
+    {
+        // Multiplying before dividing keeps the full precision of both rates.
+        let converted = amount.checked_mul(self.base_rate(to)?)?.checked_div(self.base_rate(from)?)?;
+        Some(Money::new(converted, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn code(code: &str) -> CurrencyCode {
+        CurrencyCode::from_str(code).unwrap()
+    }
+
+    fn rate_table() -> RateTable {
+        let mut rates = HashMap::new();
+        rates.insert("USD".to_string(), Decimal::ONE);
+        rates.insert("PLN".to_string(), Decimal::from_str("4.0").unwrap());
+        rates.insert("EUR".to_string(), Decimal::from_str("0.8").unwrap());
+        rates.insert("XAU".to_string(), Decimal::ZERO);
+        rates.insert("ZZZ".to_string(), Decimal::ONE);
+        RateTable::from_latest_rates(&LatestRates {
+            base: code("USD"),
+            conversion_rates: rates,
+            time_last_update_unix: None,
+            time_next_update_unix: None,
+        })
+    }
+
+    #[test]
+    fn converts_from_and_to_base() {
+        let rate_table = rate_table();
+
+        assert_eq!(rate_table.convert(Decimal::from(10), code("USD"), code("PLN")), Some(Money::new(Decimal::from(40), code("PLN"))));
+        assert_eq!(rate_table.convert(Decimal::from(40), code("PLN"), code("USD")), Some(Money::new(Decimal::from(10), code("USD"))));
+    }
+
+    #[test]
+    fn converts_through_base_cross_rate() {
+        let rate_table = rate_table();
+
+        assert_eq!(rate_table.rate(code("EUR"), code("PLN")), Some(Decimal::from(5)));
+        assert_eq!(rate_table.convert(Decimal::from(100), code("PLN"), code("EUR")), Some(Money::new(Decimal::from(20), code("EUR"))));
+    }
+
+    #[test]
+    fn unknown_or_zero_rates_are_not_converted() {
+        let rate_table = rate_table();
+
+        assert_eq!(rate_table.rates().len(), 4);
+        assert_eq!(rate_table.convert(Decimal::ONE, code("USD"), code("GBP")), None);
+        assert_eq!(rate_table.convert(Decimal::ONE, code("XAU"), code("USD")), None);
+    }
+
+    #[test]
+    fn rebases_onto_listed_currency() {
+        let rate_table = rate_table().rebase(code("EUR")).unwrap();
+
+        assert_eq!(rate_table.base(), code("EUR"));
+        assert_eq!(rate_table.rates()[&code("EUR")], Decimal::ONE);
+        assert_eq!(rate_table.rates()[&code("USD")], Decimal::from_str("1.25").unwrap());
+        assert_eq!(rate_table.rates()[&code("PLN")], Decimal::from(5));
+        assert!(rate_table.rebase(code("GBP")).is_none());
+    }
+}
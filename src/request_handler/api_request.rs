@@ -1,21 +1,110 @@
 use async_trait::async_trait;
-use crate::models::{ApiResponse, FetchResult};
+use reqwest::{Client, Response, StatusCode};
+use crate::cache::ResponseCache;
+use crate::error::ExchangeRateError;
+use crate::models::ApiResponse;
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
 
 #[async_trait]
-pub trait ApiRequest
+pub trait ApiRequest: Sync
 {
-    async fn fetch(&self) -> Result<Option<FetchResult>, Box<dyn std::error::Error>>;
+    type Output;
+
+    async fn fetch(&self) -> Result<Option<Self::Output>, ExchangeRateError>;
     fn get_url(&self) -> String;
-    async fn handle_error(&self, api_response: &ApiResponse) -> Result<(), Box<dyn std::error::Error>>
+    fn get_client(&self) -> &Client;
+    fn get_retry_policy(&self) -> &RetryPolicy;
+    fn get_rate_limiter(&self) -> Option<&RateLimiter>;
+
+    /// Requests whose responses may be served from a `ResponseCache` return it together with `cache_key`.
+    fn get_cache(&self) -> Option<&ResponseCache>
+    {
+        None
+    }
+
+    /// Identifies the response in the cache; must not contain the API key, as it may be used as a file name.
+    fn cache_key(&self) -> Option<String>
     {
-        let message_error =  match api_response.error_type.as_deref() {
-            Some("unsupported-code") => "Unsupported currency code.",
-            Some("malformed-request") => "Malformed request.",
-            Some("invalid-key") => "Invalid API key.",
-            Some("inactive-account") => "Account is inactive.",
-            Some("quota-reached") => "Request quota reached.",
-            _ => "An error occurred, but no specific error type was provided.",
+        None
+    }
+
+    /// Returns the decoded body of a successful response, from the cache if it is still fresh,
+    /// otherwise sending the request and retrying transient failures according to the retry policy.
+    async fn send(&self) -> Result<ApiResponse, ExchangeRateError>
+    {
+        let cache = match (self.get_cache(), self.cache_key()) {
+            (Some(cache), Some(cache_key)) => Some((cache, cache_key)),
+            _ => None,
         };
-        return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, message_error)));
+        if let Some((cache, cache_key)) = &cache {
+            if let Some(api_response) = cache.get(cache_key) {
+                return Ok(api_response);
+            }
+        }
+
+        let api_response = self.send_with_retries().await?;
+        if let Some((cache, cache_key)) = &cache {
+            cache.insert(cache_key, &api_response);
+        }
+        Ok(api_response)
+    }
+
+    async fn send_with_retries(&self) -> Result<ApiResponse, ExchangeRateError>
+    {
+        let retry_policy = self.get_retry_policy();
+        let mut attempt = 1;
+        loop {
+            match self.send_once().await {
+                Err(e) if retry_policy.should_retry(attempt, &e) => {
+                    tokio::time::sleep(retry_policy.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send_once(&self) -> Result<ApiResponse, ExchangeRateError>
+    {
+        if let Some(rate_limiter) = self.get_rate_limiter() {
+            rate_limiter.acquire().await?;
+        }
+
+        let response: Response = self.get_client()
+            .get(self.get_url())
+            .send()
+            .await?;
+
+        let status_code: StatusCode = response.status();
+        if status_code.is_success() {
+            let api_response: ApiResponse = response.json().await?;
+            // The open access endpoint may report errors in the body of a successful response.
+            if api_response.result == "error" {
+                return Err(self.handle_error(status_code, Some(&api_response)));
+            }
+            Ok(api_response)
+        }
+        else if status_code.is_client_error() {
+            // Rate limiting proxies and the like answer with HTML or nothing at all, which
+            // leaves the status to tell what went wrong.
+            let api_response: Option<ApiResponse> = response.json().await.ok();
+            let error = self.handle_error(status_code, api_response.as_ref());
+            if let (ExchangeRateError::QuotaReached, Some(rate_limiter)) = (&error, self.get_rate_limiter()) {
+                rate_limiter.mark_quota_reached();
+            }
+            Err(error)
+        }
+        else if status_code.is_server_error() {
+            Err(ExchangeRateError::Server(status_code))
+        }
+        else {
+            Err(ExchangeRateError::UnexpectedStatus(status_code))
+        }
+    }
+
+    fn handle_error(&self, status_code: StatusCode, api_response: Option<&ApiResponse>) -> ExchangeRateError
+    {
+        ExchangeRateError::from_error_type(api_response.and_then(|api_response| api_response.error_type.as_deref()), status_code)
     }
-}
\ No newline at end of file
+}
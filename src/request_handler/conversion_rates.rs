@@ -1,63 +1,115 @@
-use crate::models::{ApiResponse, FetchResult};
+use crate::cache::{endpoint_tag, ResponseCache};
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::LatestRates;
 use crate::request_handler::ApiRequest;
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
 
-use reqwest::{Client, Response, StatusCode};
+use reqwest::Client;
 use async_trait::async_trait;
 pub struct ConversionRates
 {
     url: String,
+    endpoint: String,
+    source: CurrencyCode,
+    client: Client,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
 }
 
 impl  ConversionRates {
-    pub fn new(source: &String, api_key: &String, prefix: &String) -> Self
+    pub fn new(source: &CurrencyCode, api_key: &String, prefix: &String) -> Self
+    {
+        Self::with_client(Client::new(), source, api_key, prefix)
+    }
+
+    pub fn with_client(client: Client, source: &CurrencyCode, api_key: &String, prefix: &String) -> Self
     {
         let url_ = format!("{prefix}/v6/{}/latest/{}", api_key, source);
         Self {
-            url: url_
+            url: url_,
+            endpoint: endpoint_tag(prefix, api_key),
+            source: *source,
+            client,
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            cache: None,
         }
     }
+
+    /// Uses the keyless endpoint, e.g. `OPEN_ACCESS_PREFIX`, instead of the one authorised by an API key.
+    pub fn open_access(client: Client, source: &CurrencyCode, prefix: &String) -> Self
+    {
+        let url_ = format!("{prefix}/v6/latest/{}", source);
+        Self {
+            url: url_,
+            ..Self::with_client(client, source, &String::new(), prefix)
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self
+    {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self
+    {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self
+    {
+        self.cache = Some(cache);
+        self
+    }
 }
 
 #[async_trait]
 impl ApiRequest for ConversionRates 
 {
+    type Output = LatestRates;
+
     fn get_url(&self) -> String {
         self.url.clone()
     }
 
-    async fn fetch(&self) -> Result<Option<FetchResult>, Box<dyn std::error::Error>> {
-        let response: Response = Client::new()
-            .get(self.get_url())
-            .send()
-            .await?;
-
-        let status_code: StatusCode = response.status();
-        if status_code.is_success() {
-            let api_response: ApiResponse = response.json().await?;
-            if let Some(supprted_codes) = api_response.conversion_rates {
-                return Ok(Some(FetchResult::HashMapRates(supprted_codes)));
-            }
-            else {
-                return Ok(None);
-            }
-        }
-        else if status_code.is_client_error() {
-            let api_response: ApiResponse = response.json().await?;
-            let error =  self.handle_error(&api_response).await;
-            match error{
-                Ok(_) => Ok(None),
-                Err(e) =>Err(e),
-            }
-        }
-        else if status_code.is_server_error() {
-            return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("Server Error: {}", status_code))));
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    fn get_cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("latest_{}_{}", self.endpoint, self.source))
+    }
+
+    async fn fetch(&self) -> Result<Option<LatestRates>, ExchangeRateError> {
+        let api_response = self.send().await?;
+        if let Some(conversion_rates) = api_response.conversion_rates {
+            Ok(Some(LatestRates {
+                base: self.source,
+                conversion_rates,
+                time_last_update_unix: api_response.time_last_update_unix,
+                time_next_update_unix: api_response.time_next_update_unix,
+            }))
         }
         else {
-            return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("Unexpected response status: {}", status_code))));
-
+            Ok(None)
         }
-
-    
     }
 }
 
@@ -102,10 +154,10 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let source = "USD".to_string();
+        let source = CurrencyCode::from_str("USD").unwrap();
         let api_key = "111111111111111111111111".to_string();
 
-        let prefix = format!("{}", mock_server.uri()).to_string();
+        let prefix = mock_server.uri();
 
         let standard_request = ConversionRates::new(&source, &api_key, &prefix);
 
@@ -117,8 +169,9 @@ mod tests {
         expected_hashmap.insert("AMD".to_string(), Decimal::from_str("399.0437").unwrap());
 
         match standard_request.fetch().await {
-            Ok(Some(FetchResult::HashMapRates(convertion_result))) => {
-                
+            Ok(Some(latest_rates)) => {
+                let convertion_result = latest_rates.conversion_rates;
+                assert_eq!(latest_rates.base, source);
                 assert_eq!(convertion_result.len(), expected_hashmap.len());
 
                 for (currency, rate) in &expected_hashmap {
@@ -133,7 +186,6 @@ mod tests {
             Err(e) => {
                 panic!("Error fetching conversion rate: {}", e);
             },
-            _ => (),
         }
     }
 
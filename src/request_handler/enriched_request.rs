@@ -0,0 +1,145 @@
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::EnrichedConversion;
+use crate::request_handler::ApiRequest;
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+
+use reqwest::Client;
+use async_trait::async_trait;
+
+pub struct EnrichedRequest {
+    url: String,
+    source: CurrencyCode,
+    target: CurrencyCode,
+    client: Client,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl EnrichedRequest
+{
+    pub fn new(source: &CurrencyCode, target: &CurrencyCode, api_key: &String, prefix: &String) -> Self {
+        Self::with_client(Client::new(), source, target, api_key, prefix)
+    }
+
+    pub fn with_client(client: Client, source: &CurrencyCode, target: &CurrencyCode, api_key: &String, prefix: &String) -> Self {
+        let url_ = format!("{prefix}/v6/{}/enriched/{}/{}", api_key, source, target);
+        Self {
+            url: url_,
+            source: *source,
+            target: *target,
+            client,
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+}
+
+#[async_trait]
+impl ApiRequest for EnrichedRequest {
+    type Output = EnrichedConversion;
+
+    fn get_url(&self) -> String {
+        self.url.clone()
+    }
+
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    async fn fetch(&self) -> Result<Option<EnrichedConversion>, ExchangeRateError> {
+        let api_response = self.send().await?;
+        if let (Some(conversion_rate), Some(target_data)) = (api_response.conversion_rate, api_response.target_data) {
+            Ok(Some(EnrichedConversion {
+                base: self.source,
+                target: self.target,
+                conversion_rate,
+                target_data,
+                time_last_update_unix: api_response.time_last_update_unix,
+                time_next_update_unix: api_response.time_next_update_unix,
+            }))
+        }
+        else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{MockServer, Mock, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_positive_fetch_enriched_request() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "documentation": "https://www.exchangerate-api.com/docs",
+            "terms_of_use": "https://www.exchangerate-api.com/terms",
+            "time_last_update_unix": 1711238401,
+            "time_last_update_utc": "Sun, 24 Mar 2024 00:00:01 +0000",
+            "time_next_update_unix": 1711324801,
+            "time_next_update_utc": "Mon, 25 Mar 2024 00:00:01 +0000",
+            "base_code": "GBP",
+            "target_code": "JPY",
+            "conversion_rate": 142.0543,
+            "target_data": {
+                "locale": "Japan",
+                "two_letter_code": "JP",
+                "currency_name": "Japanese Yen",
+                "currency_name_short": "Yen",
+                "display_symbol": "00A5",
+                "flag_url": "https://www.exchangerate-api.com/img/docs/JP.gif"
+            }
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/enriched/GBP/JPY"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .mount(&mock_server)
+            .await;
+
+        let source = CurrencyCode::from_str("GBP").unwrap();
+        let target = CurrencyCode::from_str("JPY").unwrap();
+        let api_key = "111111111111111111111111".to_string();
+
+        let prefix = mock_server.uri();
+
+        let enriched_request = EnrichedRequest::new(&source, &target, &api_key, &prefix);
+
+        match enriched_request.fetch().await {
+            Ok(Some(enriched_conversion)) => {
+                assert_eq!(enriched_conversion.conversion_rate, Decimal::from_str("142.0543").unwrap());
+                assert_eq!(enriched_conversion.target_data.currency_name, "Japanese Yen");
+                assert_eq!(enriched_conversion.target_data.two_letter_code, "JP");
+                assert_eq!(enriched_conversion.target_data.symbol().as_deref(), Some("¥"));
+            },
+            Ok(None) => panic!("Incorrect Source/Target"),
+            Err(e) => panic!("Error fetching enriched data: {}", e),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::cache::{endpoint_tag, ResponseCache};
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::DatedRates;
+use crate::request_handler::ApiRequest;
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+
+use reqwest::Client;
+use rust_decimal::Decimal;
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate};
+
+pub struct HistoricalRates {
+    url: String,
+    endpoint: String,
+    source: CurrencyCode,
+    date: NaiveDate,
+    amount: Option<Decimal>,
+    client: Client,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+}
+
+impl HistoricalRates
+{
+    /// Rates of `source` on `date`; with `amount`, the API returns that amount converted instead of rates.
+    pub fn new(source: &CurrencyCode, date: &NaiveDate, amount: Option<&Decimal>, api_key: &String, prefix: &String) -> Self {
+        Self::with_client(Client::new(), source, date, amount, api_key, prefix)
+    }
+
+    pub fn with_client(client: Client, source: &CurrencyCode, date: &NaiveDate, amount: Option<&Decimal>, api_key: &String, prefix: &String) -> Self {
+        let mut url_ = format!("{prefix}/v6/{}/history/{}/{}/{}/{}", api_key, source, date.year(), date.month(), date.day());
+        if let Some(amount) = amount {
+            url_ = format!("{}/{}", url_, amount);
+        }
+        Self {
+            url: url_,
+            endpoint: endpoint_tag(prefix, api_key),
+            source: *source,
+            date: *date,
+            amount: amount.copied(),
+            client,
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            cache: None,
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+}
+
+#[async_trait]
+impl ApiRequest for HistoricalRates {
+    type Output = DatedRates;
+
+    fn get_url(&self) -> String {
+        self.url.clone()
+    }
+
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    fn get_cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        match self.amount {
+            Some(amount) => Some(format!("history_{}_{}_{}_{}", self.endpoint, self.source, self.date, amount)),
+            None => Some(format!("history_{}_{}_{}", self.endpoint, self.source, self.date)),
+        }
+    }
+
+    async fn fetch(&self) -> Result<Option<DatedRates>, ExchangeRateError> {
+        let api_response = self.send().await?;
+        if api_response.conversion_rates.is_none() && api_response.conversion_amounts.is_none() {
+            return Ok(None);
+        }
+
+        let date = match (api_response.year, api_response.month, api_response.day) {
+            (Some(year), Some(month), Some(day)) => NaiveDate::from_ymd_opt(year, month, day).unwrap_or(self.date),
+            _ => self.date,
+        };
+        Ok(Some(DatedRates {
+            base: self.source,
+            date,
+            conversion_rates: api_response.conversion_rates,
+            conversion_amounts: api_response.conversion_amounts,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{MockServer, Mock, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_positive_fetch_historical_rates() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "documentation": "https://www.exchangerate-api.com/docs",
+            "terms_of_use": "https://www.exchangerate-api.com/terms",
+            "year": 2024,
+            "month": 3,
+            "day": 1,
+            "base_code": "USD",
+            "conversion_rates": {
+                "USD": 1,
+                "EUR": 0.9246,
+                "PLN": 3.9907
+            }
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/history/USD/2024/3/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .mount(&mock_server)
+            .await;
+
+        let source = CurrencyCode::from_str("USD").unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let api_key = "111111111111111111111111".to_string();
+
+        let prefix = mock_server.uri();
+
+        let historical_rates = HistoricalRates::new(&source, &date, None, &api_key, &prefix);
+
+        match historical_rates.fetch().await {
+            Ok(Some(dated_rates)) => {
+                assert_eq!(dated_rates.date, date);
+                let conversion_rates = dated_rates.conversion_rates.unwrap();
+                assert_eq!(conversion_rates["PLN"], Decimal::from_str("3.9907").unwrap());
+                assert!(dated_rates.conversion_amounts.is_none());
+            },
+            Ok(None) => panic!("Incorrect Source/Date"),
+            Err(e) => panic!("Error fetching historical rates: {}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_positive_fetch_historical_amounts() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "year": 2024,
+            "month": 3,
+            "day": 1,
+            "base_code": "USD",
+            "requested_amount": 100,
+            "conversion_amounts": {
+                "USD": 100,
+                "PLN": 399.07
+            }
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/history/USD/2024/3/1/100"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .mount(&mock_server)
+            .await;
+
+        let source = CurrencyCode::from_str("USD").unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let amount = Decimal::from(100);
+        let api_key = "111111111111111111111111".to_string();
+
+        let prefix = mock_server.uri();
+
+        let historical_rates = HistoricalRates::new(&source, &date, Some(&amount), &api_key, &prefix);
+
+        match historical_rates.fetch().await {
+            Ok(Some(dated_rates)) => {
+                let conversion_amounts = dated_rates.conversion_amounts.unwrap();
+                assert_eq!(conversion_amounts["PLN"], Decimal::from_str("399.07").unwrap());
+            },
+            Ok(None) => panic!("Incorrect Source/Date"),
+            Err(e) => panic!("Error fetching historical rates: {}", e),
+        }
+    }
+}
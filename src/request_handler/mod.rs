@@ -2,8 +2,14 @@ mod api_request;
 mod standard_request;
 mod supported_codes;
 mod conversion_rates;
+mod enriched_request;
+mod historical_rates;
+mod quota_request;
 
 pub use api_request::ApiRequest;
 pub use standard_request::StandardRequest;
 pub use supported_codes::SupportedCodes;
-pub use conversion_rates::ConversionRates;
\ No newline at end of file
+pub use conversion_rates::ConversionRates;
+pub use enriched_request::EnrichedRequest;
+pub use historical_rates::HistoricalRates;
+pub use quota_request::QuotaRequest;
\ No newline at end of file
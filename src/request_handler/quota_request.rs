@@ -0,0 +1,112 @@
+use crate::error::ExchangeRateError;
+use crate::models::QuotaStatus;
+use crate::request_handler::ApiRequest;
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+
+use reqwest::Client;
+use async_trait::async_trait;
+
+/// Not subject to the client's `RateLimiter`: the quota status is free and most needed
+/// once the local quota is used up.
+pub struct QuotaRequest {
+    url: String,
+    client: Client,
+    retry_policy: RetryPolicy,
+}
+
+impl QuotaRequest {
+    pub fn new(api_key: &String, prefix: &String) -> Self {
+        Self::with_client(Client::new(), api_key, prefix)
+    }
+
+    pub fn with_client(client: Client, api_key: &String, prefix: &String) -> Self {
+        let url_ = format!("{prefix}/v6/{}/quota", api_key);
+        Self {
+            url: url_,
+            client,
+            retry_policy: RetryPolicy::none(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+}
+
+#[async_trait]
+impl ApiRequest for QuotaRequest {
+    type Output = QuotaStatus;
+
+    fn get_url(&self) -> String {
+        self.url.clone()
+    }
+
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        None
+    }
+
+    async fn fetch(&self) -> Result<Option<QuotaStatus>, ExchangeRateError> {
+        let api_response = self.send().await?;
+        if let (Some(plan_quota), Some(requests_remaining), Some(refresh_day_of_month)) =
+            (api_response.plan_quota, api_response.requests_remaining, api_response.refresh_day_of_month) {
+            Ok(Some(QuotaStatus {
+                plan_quota,
+                requests_remaining,
+                refresh_day_of_month,
+            }))
+        }
+        else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{MockServer, Mock, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+
+    #[tokio::test]
+    async fn test_positive_fetch_quota_request() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "documentation": "https://www.exchangerate-api.com/docs",
+            "terms_of_use": "https://www.exchangerate-api.com/terms",
+            "plan_quota": 30000,
+            "requests_remaining": 25623,
+            "refresh_day_of_month": 17
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/quota"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .mount(&mock_server)
+            .await;
+
+        let api_key = "111111111111111111111111".to_string();
+        let prefix = mock_server.uri();
+
+        let quota_request = QuotaRequest::new(&api_key, &prefix);
+
+        match quota_request.fetch().await {
+            Ok(Some(quota_status)) => {
+                assert_eq!(quota_status, QuotaStatus { plan_quota: 30000, requests_remaining: 25623, refresh_day_of_month: 17 });
+            },
+            Ok(None) => panic!("No quota data"),
+            Err(e) => panic!("Error fetching quota: {}", e),
+        }
+    }
+}
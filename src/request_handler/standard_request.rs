@@ -1,83 +1,91 @@
-use crate::models::{ApiResponse, FetchResult};
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::models::PairConversion;
+use crate::money::Money;
 use crate::request_handler::ApiRequest;
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
 
-use reqwest::{Client, Response, StatusCode};
+use reqwest::Client;
 use rust_decimal::Decimal;
 use async_trait::async_trait;
-use chrono::{TimeZone, Utc};
 
 pub struct StandardRequest {
     url: String,
+    source: CurrencyCode,
+    target: CurrencyCode,
+    client: Client,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
 }
 
 impl StandardRequest
 {
-    pub fn new(source: &String, target: &String, decimal_value: &Decimal, api_key: &String, prefix: &String) -> Self {
+    pub fn new(source: &CurrencyCode, target: &CurrencyCode, decimal_value: &Decimal, api_key: &String, prefix: &String) -> Self {
+        Self::with_client(Client::new(), source, target, decimal_value, api_key, prefix)
+    }
+
+    pub fn with_client(client: Client, source: &CurrencyCode, target: &CurrencyCode, decimal_value: &Decimal, api_key: &String, prefix: &String) -> Self {
         let _url = format!("{prefix}/v6/{}/pair/{}/{}/{}", api_key, source, target, decimal_value);
         Self {
             url: _url,
+            source: *source,
+            target: *target,
+            client,
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
         }
     }
-    
-    fn print_time(&self, api_response: &ApiResponse) {
-    
-        if let (Some(time_last_update_unix), Some(time_next_update_unix)) = (api_response.time_last_update_unix, api_response.time_next_update_unix) {
-            let time_last_update = Utc.timestamp_opt(time_last_update_unix, 0).unwrap();
-            let time_next_update = Utc.timestamp_opt(time_next_update_unix, 0).unwrap();
 
-            println!("Time of last update: {}", time_last_update);
-            println!("Time of next update: {}", time_next_update);
-        } 
-        else {
-            println!("Dates are not available");
-        }
-        
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
     }
 
 }
 
 #[async_trait]
 impl ApiRequest for StandardRequest {
+    type Output = PairConversion;
+
     fn get_url(&self) -> String {
         self.url.clone()
     }
 
-    async fn fetch(&self) -> Result<Option<FetchResult>, Box<dyn std::error::Error>> {
-        println!("{}", self.url);
-        let response: Response = Client::new()
-            .get(self.get_url())
-            .send()
-            .await?;
-
-        let status_code: StatusCode = response.status();
-
-        if status_code.is_success() {
-            let api_response: ApiResponse = response.json().await?;
-            self.print_time(&api_response);
-            if let Some(convertion_result) = api_response.conversion_result {
-                return Ok(Some(FetchResult::Decimal(convertion_result)));
-            }
-            else {
-                return Ok(None);
-            }
-        }
-        else if status_code.is_client_error() {
-            let api_response: ApiResponse = response.json().await?;
-            let error =  self.handle_error(&api_response).await;
-            match error {
-                Ok(_) => Ok(None),
-                Err(e) =>Err(e),
-            }
-        }
-        else if status_code.is_server_error() {
-            println!("{}",status_code);
-            return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("Server Error: {}", status_code))));
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    async fn fetch(&self) -> Result<Option<PairConversion>, ExchangeRateError> {
+        let api_response = self.send().await?;
+        if let (Some(conversion_rate), Some(convertion_result)) = (api_response.conversion_rate, api_response.conversion_result) {
+            let base = api_response.base_code.and_then(|code| code.parse().ok()).unwrap_or(self.source);
+            let target = api_response.target_code.and_then(|code| code.parse().ok()).unwrap_or(self.target);
+            Ok(Some(PairConversion {
+                base,
+                target,
+                conversion_rate,
+                result: Money::new(convertion_result, target),
+                time_last_update_unix: api_response.time_last_update_unix,
+                time_next_update_unix: api_response.time_next_update_unix,
+            }))
         }
         else {
-            return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("Unexpected response status: {}", status_code))));
-
+            Ok(None)
         }
-        
     }
 }
 
@@ -88,6 +96,7 @@ mod tests {
     use wiremock::matchers::{method, path};
     use rust_decimal::Decimal;
     use std::str::FromStr;
+    use std::time::Duration;
 
 
     #[tokio::test]
@@ -116,19 +125,24 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let source = "USD".to_string();
-        let target= "PLN".to_string();
+        let source = CurrencyCode::from_str("USD").unwrap();
+        let target = CurrencyCode::from_str("PLN").unwrap();
         let decimal_value = Decimal::from_str("500").unwrap();
         let api_key = "111111111111111111111111".to_string();
 
-        let prefix = format!("{}", mock_server.uri());
+        let prefix = mock_server.uri();
 
         let standard_request = StandardRequest::new(&source, &target, &decimal_value, &api_key, &prefix);
 
         match standard_request.fetch().await {
-            Ok(Some(FetchResult::Decimal(convertion_result))) => {
+            Ok(Some(pair_conversion)) => {
                 let expected_value = Decimal::from_str("1994.35").unwrap(); 
-                assert_eq!(convertion_result, expected_value);
+                assert_eq!(pair_conversion.result.amount, expected_value);
+                assert_eq!(pair_conversion.result.currency, target);
+                assert_eq!(pair_conversion.base, source);
+                assert_eq!(pair_conversion.conversion_rate, Decimal::from_str("3.9887").unwrap());
+                assert_eq!(pair_conversion.last_update().unwrap().to_rfc2822(), "Sun, 24 Mar 2024 00:00:01 +0000");
+                assert_eq!(pair_conversion.time_next_update_unix, Some(1711324801));
     
             },
             Ok(None) => {
@@ -137,7 +151,6 @@ mod tests {
             Err(e) => {
                 panic!("Error fetching conversion rate: {}", e);
             },
-            _ => (),
         }
     }
 
@@ -151,28 +164,156 @@ mod tests {
         .mount(&mock_server)
         .await;
 
-        let source = "USD".to_string();
-        let target= "PLN".to_string();
+        let source = CurrencyCode::from_str("USD").unwrap();
+        let target = CurrencyCode::from_str("PLN").unwrap();
         let decimal_value = Decimal::from_str("500").unwrap();
         let api_key = "111111111111111111111111".to_string();
 
-        let prefix = format!("{}", mock_server.uri());
+        let prefix = mock_server.uri();
 
         let standard_request = StandardRequest::new(&source, &target, &decimal_value, &api_key, &prefix);
         
         match standard_request.fetch().await {
-            Ok(Some(FetchResult::Decimal(_convertion_result))) => {
+            Ok(Some(_pair_conversion)) => {
                 panic!("The test failed: a server error was expected, but success was received.")
             },
             Ok(None) => {
                 panic!("Incorrect Source/Target");
             },
             Err(e) => {
+                assert!(matches!(e, ExchangeRateError::Server(reqwest::StatusCode::INTERNAL_SERVER_ERROR)));
                 assert_eq!(e.to_string(), "Server Error: 500 Internal Server Error")
             },
-            _ => (),
         }
         
     }
+
+    #[tokio::test]
+    async fn fetch_retries_server_errors_until_success() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "base_code": "USD",
+            "target_code": "PLN",
+            "conversion_rate": 3.9887,
+            "conversion_result": 1994.35
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/pair/USD/PLN/500"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/pair/USD/PLN/500"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let source = CurrencyCode::from_str("USD").unwrap();
+        let target = CurrencyCode::from_str("PLN").unwrap();
+        let decimal_value = Decimal::from_str("500").unwrap();
+        let api_key = "111111111111111111111111".to_string();
+
+        let prefix = mock_server.uri();
+        let retry_policy = RetryPolicy::new(3, Duration::from_millis(1), Duration::from_millis(5), 0.5);
+
+        let standard_request = StandardRequest::new(&source, &target, &decimal_value, &api_key, &prefix)
+            .with_retry_policy(retry_policy);
+
+        match standard_request.fetch().await {
+            Ok(Some(pair_conversion)) => {
+                assert_eq!(pair_conversion.result.amount, Decimal::from_str("1994.35").unwrap());
+            },
+            Ok(_) => panic!("Incorrect Source/Target"),
+            Err(e) => panic!("Error fetching conversion rate: {}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_retries_rate_limiting_without_json_body() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "success",
+            "base_code": "USD",
+            "target_code": "PLN",
+            "conversion_rate": 3.9887,
+            "conversion_result": 1994.35
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/pair/USD/PLN/500"))
+            .respond_with(ResponseTemplate::new(429).set_body_string("Too Many Requests"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/pair/USD/PLN/500"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body_mock_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let source = CurrencyCode::from_str("USD").unwrap();
+        let target = CurrencyCode::from_str("PLN").unwrap();
+        let decimal_value = Decimal::from_str("500").unwrap();
+        let api_key = "111111111111111111111111".to_string();
+
+        let prefix = mock_server.uri();
+        let retry_policy = RetryPolicy::new(3, Duration::from_millis(1), Duration::from_millis(5), 0.5);
+
+        let standard_request = StandardRequest::new(&source, &target, &decimal_value, &api_key, &prefix)
+            .with_retry_policy(retry_policy);
+
+        match standard_request.fetch().await {
+            Ok(Some(pair_conversion)) => {
+                assert_eq!(pair_conversion.result.amount, Decimal::from_str("1994.35").unwrap());
+            },
+            Ok(_) => panic!("Incorrect Source/Target"),
+            Err(e) => panic!("Error fetching conversion rate: {}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_does_not_retry_unsupported_code() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "error",
+            "error-type": "unsupported-code"
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/111111111111111111111111/pair/USD/VED/500"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(body_mock_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let source = CurrencyCode::from_str("USD").unwrap();
+        let target = CurrencyCode::from_str("VED").unwrap();
+        let decimal_value = Decimal::from_str("500").unwrap();
+        let api_key = "111111111111111111111111".to_string();
+
+        let prefix = mock_server.uri();
+        let retry_policy = RetryPolicy::new(5, Duration::from_millis(1), Duration::from_millis(5), 0.0);
+
+        let standard_request = StandardRequest::new(&source, &target, &decimal_value, &api_key, &prefix)
+            .with_retry_policy(retry_policy);
+
+        match standard_request.fetch().await {
+            Err(ExchangeRateError::UnsupportedCode) => (),
+            Err(e) => panic!("Expected UnsupportedCode, got: {}", e),
+            Ok(_) => panic!("The test failed: an error was expected, but success was received."),
+        }
+    }
     
 }
@@ -1,60 +1,131 @@
-use crate::models::{ApiResponse, FetchResult};
+use crate::cache::{endpoint_tag, ResponseCache};
+use crate::error::ExchangeRateError;
+use crate::models::SupportedCurrency;
 use crate::request_handler::ApiRequest;
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
 
-use reqwest::{Client, Response, StatusCode};
+use reqwest::Client;
 use async_trait::async_trait;
 
 pub struct SupportedCodes {
-    url: String
+    url: String,
+    endpoint: String,
+    client: Client,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
 }
 
 impl SupportedCodes {
     pub fn new(api_key: &String, prefix: &String) -> Self {
+        Self::with_client(Client::new(), api_key, prefix)
+    }
+
+    pub fn with_client(client: Client, api_key: &String, prefix: &String) -> Self {
         let url_ = format!("{prefix}/v6/{}/codes", api_key);
         Self {
             url: url_,
+            endpoint: endpoint_tag(prefix, api_key),
+            client,
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            cache: None,
         }
     }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 }
 
 #[async_trait]
 impl ApiRequest for SupportedCodes {
+    type Output = Vec<SupportedCurrency>;
+
     fn get_url(&self) -> String {
         self.url.clone()
     }
 
-    async fn fetch(&self) -> Result<Option<FetchResult>, Box<dyn std::error::Error>> {
-        let response: Response = Client::new()
-            .get(self.get_url())
-            .send()
-            .await?;
-        let status_code: StatusCode = response.status();
-        if status_code.is_success() {
-            let api_response: ApiResponse = response.json().await?;
-            if let Some(supprted_codes) = api_response.supported_codes {
-                return Ok(Some(FetchResult::VecString(supprted_codes)));
-            }
-            else {
-                return Ok(None);
-            }
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
 
-        }
-        else if status_code.is_client_error() {
-            let api_response: ApiResponse = response.json().await?;
-            let error =  self.handle_error(&api_response).await;
-            match error {
-                Ok(_) => Ok(None),
-                Err(e) => Err(e),
-            }
-        }
-        else if status_code.is_server_error() {
-            return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("Server Error: {}", status_code))));
+    fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    fn get_cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("codes_{}", self.endpoint))
+    }
+
+    async fn fetch(&self) -> Result<Option<Vec<SupportedCurrency>>, ExchangeRateError> {
+        let api_response = self.send().await?;
+        if let Some(supported_codes) = api_response.supported_codes {
+            Ok(Some(supported_codes.into_iter()
+                .filter_map(|currency_pair| match currency_pair.as_slice() {
+                    [code, name] => Some(SupportedCurrency { code: code.clone(), name: name.clone() }),
+                    _ => None,
+                })
+                .collect()))
         }
         else {
-            return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("Unexpected response status: {}", status_code))));
-
+            Ok(None)
         }
-
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{MockServer, Mock, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+
+    #[tokio::test]
+    async fn fetch_maps_invalid_key_error() {
+        let mock_server = MockServer::start().await;
+        let body_mock_response = r#"
+        {
+            "result": "error",
+            "documentation": "https://www.exchangerate-api.com/docs",
+            "terms-of-use": "https://www.exchangerate-api.com/terms",
+            "error-type": "invalid-key"
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/v6/invalid_key/codes"))
+            .respond_with(ResponseTemplate::new(403).set_body_string(body_mock_response))
+            .mount(&mock_server)
+            .await;
+
+        let api_key = "invalid_key".to_string();
+        let prefix = mock_server.uri();
+
+        let supported_codes = SupportedCodes::new(&api_key, &prefix);
+
+        match supported_codes.fetch().await {
+            Err(ExchangeRateError::InvalidKey) => (),
+            Err(e) => panic!("Expected InvalidKey, got: {}", e),
+            Ok(_) => panic!("The test failed: an invalid key error was expected, but success was received."),
+        }
+    }
+}
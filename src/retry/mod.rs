@@ -0,0 +1,129 @@
+use crate::error::ExchangeRateError;
+
+use rand::Rng;
+use reqwest::StatusCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub type RetryPredicate = Arc<dyn Fn(&ExchangeRateError) -> bool + Send + Sync>;
+
+/// Decides how often and how long to wait before a failed request is sent again.
+///
+/// The delay before attempt `n + 1` is `base_delay * 2^(n - 1)`, capped at `max_delay`,
+/// then reduced by a random fraction of up to `jitter` (0.0 - 1.0).
+#[derive(Clone)]
+pub struct RetryPolicy
+{
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: f64,
+    retry_on: RetryPredicate,
+}
+
+impl RetryPolicy
+{
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration, jitter: f64) -> Self
+    {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay,
+            jitter: jitter.clamp(0.0, 1.0),
+            retry_on: Arc::new(is_transient),
+        }
+    }
+
+    /// A policy that sends every request exactly once.
+    pub fn none() -> Self
+    {
+        Self::new(1, Duration::ZERO, Duration::ZERO, 0.0)
+    }
+
+    /// Replaces the default `is_transient` predicate.
+    pub fn retry_on<F>(mut self, retry_on: F) -> Self
+    where
+        F: Fn(&ExchangeRateError) -> bool + Send + Sync + 'static,
+    {
+        self.retry_on = Arc::new(retry_on);
+        self
+    }
+
+    pub fn should_retry(&self, attempt: u32, error: &ExchangeRateError) -> bool
+    {
+        attempt < self.max_attempts && (self.retry_on)(error)
+    }
+
+    pub fn delay(&self, attempt: u32) -> Duration
+    {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self.base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter > 0.0 {
+            delay.mul_f64(1.0 - self.jitter * rand::thread_rng().gen::<f64>())
+        }
+        else {
+            delay
+        }
+    }
+}
+
+impl Default for RetryPolicy
+{
+    fn default() -> Self
+    {
+        Self::new(3, Duration::from_millis(500), Duration::from_secs(10), 0.5)
+    }
+}
+
+/// Server errors, rate limiting and transport failures (connection resets, timeouts)
+/// are worth another attempt; errors reported by the API about the request itself,
+/// such as `invalid-key` or `unsupported-code`, are not.
+pub fn is_transient(error: &ExchangeRateError) -> bool
+{
+    match error {
+        ExchangeRateError::Server(_) => true,
+        ExchangeRateError::Transport(e) => !e.is_builder(),
+        ExchangeRateError::UnexpectedStatus(status_code) => *status_code == StatusCode::TOO_MANY_REQUESTS,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_grows_exponentially_up_to_max_delay() {
+        let retry_policy = RetryPolicy::new(10, Duration::from_millis(100), Duration::from_millis(500), 0.0);
+
+        assert_eq!(retry_policy.delay(1), Duration::from_millis(100));
+        assert_eq!(retry_policy.delay(2), Duration::from_millis(200));
+        assert_eq!(retry_policy.delay(3), Duration::from_millis(400));
+        assert_eq!(retry_policy.delay(4), Duration::from_millis(500));
+        assert_eq!(retry_policy.delay(40), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn jitter_never_exceeds_computed_delay() {
+        let retry_policy = RetryPolicy::new(10, Duration::from_millis(100), Duration::from_secs(1), 0.5);
+
+        for _ in 0..100 {
+            let delay = retry_policy.delay(2);
+            assert!(delay <= Duration::from_millis(200));
+            assert!(delay >= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn api_errors_are_not_retried() {
+        let retry_policy = RetryPolicy::default();
+
+        assert!(retry_policy.should_retry(1, &ExchangeRateError::Server(StatusCode::BAD_GATEWAY)));
+        assert!(!retry_policy.should_retry(3, &ExchangeRateError::Server(StatusCode::BAD_GATEWAY)));
+        assert!(!retry_policy.should_retry(1, &ExchangeRateError::InvalidKey));
+        assert!(!retry_policy.should_retry(1, &ExchangeRateError::UnsupportedCode));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::currency::CurrencyCode;
+use crate::error::ExchangeRateError;
+use crate::file;
+use crate::models::LatestRates;
+use crate::provider::RateProvider;
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Bumped whenever the file layout changes incompatibly.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Latest rates for one base, with the rate map sorted by code so that
+/// consecutive snapshots diff cleanly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaseRates
+{
+    pub base: CurrencyCode,
+    pub time_last_update_unix: Option<i64>,
+    pub time_next_update_unix: Option<i64>,
+    pub conversion_rates: BTreeMap<String, Decimal>,
+}
+
+impl From<LatestRates> for BaseRates
+{
+    fn from(latest_rates: LatestRates) -> Self
+    {
+        Self {
+            base: latest_rates.base,
+            time_last_update_unix: latest_rates.time_last_update_unix,
+            time_next_update_unix: latest_rates.time_next_update_unix,
+            conversion_rates: latest_rates.conversion_rates.into_iter().collect(),
+        }
+    }
+}
+
+impl From<BaseRates> for LatestRates
+{
+    fn from(base_rates: BaseRates) -> Self
+    {
+        Self {
+            base: base_rates.base,
+            conversion_rates: base_rates.conversion_rates.into_iter().collect(),
+            time_last_update_unix: base_rates.time_last_update_unix,
+            time_next_update_unix: base_rates.time_next_update_unix,
+        }
+    }
+}
+
+/// Latest rates for several bases, saved by the `snapshot` command and read back by `--offline`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatesSnapshot
+{
+    pub version: u32,
+    pub created_unix: i64,
+    pub rates: Vec<BaseRates>,
+}
+
+impl RatesSnapshot
+{
+    pub fn new(rates: Vec<LatestRates>) -> Self
+    {
+        Self {
+            version: SNAPSHOT_VERSION,
+            created_unix: Utc::now().timestamp(),
+            rates: rates.into_iter().map(BaseRates::from).collect(),
+        }
+    }
+
+    /// Fetches the latest rates of every base, failing if any of them is unavailable.
+    pub async fn fetch(provider: &dyn RateProvider, bases: &[CurrencyCode]) -> Result<Self, ExchangeRateError>
+    {
+        let mut rates = Vec::with_capacity(bases.len());
+        for base in bases {
+            match provider.latest_rates(*base).await? {
+                Some(latest_rates) => rates.push(latest_rates),
+                None => return Err(ExchangeRateError::InvalidResponse(format!("no rates for {}", base))),
+            }
+        }
+        Ok(Self::new(rates))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ExchangeRateError>
+    {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ExchangeRateError>
+    {
+        let snapshot: RatesSnapshot = serde_json::from_str(json)
+            .map_err(|e| ExchangeRateError::InvalidResponse(format!("snapshot: {}", e)))?;
+        if snapshot.version > SNAPSHOT_VERSION {
+            return Err(ExchangeRateError::InvalidResponse(format!("snapshot version {} is newer than {}", snapshot.version, SNAPSHOT_VERSION)));
+        }
+        Ok(snapshot)
+    }
+
+    /// Writes to a temporary file next to `path` and renames it over `path`,
+    /// so readers never see a partially written snapshot.
+    pub fn write(&self, path: &Path) -> Result<(), ExchangeRateError>
+    {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| ExchangeRateError::InvalidResponse(format!("snapshot: {}", e)))?;
+        Ok(file::write_atomically(path, &(json + "\n"))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::OfflineProvider;
+    use std::str::FromStr;
+
+    fn latest_rates(base: &str, rates: &[(&str, &str)]) -> LatestRates {
+        LatestRates {
+            base: CurrencyCode::from_str(base).unwrap(),
+            conversion_rates: rates.iter().map(|(code, rate)| (code.to_string(), Decimal::from_str(rate).unwrap())).collect(),
+            time_last_update_unix: Some(1711065601),
+            time_next_update_unix: Some(1711152001),
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_every_base() {
+        let provider = OfflineProvider::from_latest_rates(vec![latest_rates("USD", &[("USD", "1"), ("PLN", "4"), ("EUR", "0.8")])]);
+
+        let snapshot = RatesSnapshot::fetch(&provider, &[CurrencyCode::USD, CurrencyCode::EUR]).await.unwrap();
+        assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+        assert_eq!(snapshot.rates.len(), 2);
+        assert_eq!(snapshot.rates[1].base, CurrencyCode::EUR);
+        assert_eq!(snapshot.rates[1].conversion_rates["PLN"], Decimal::from(5));
+
+        let gbp = CurrencyCode::from_str("GBP").unwrap();
+        assert!(RatesSnapshot::fetch(&provider, &[CurrencyCode::USD, gbp]).await.is_err());
+    }
+
+    #[test]
+    fn writes_sorted_rates_and_reads_them_back() {
+        let path = std::env::temp_dir().join(format!("exchange_rate_api_snapshot_{}.json", std::process::id()));
+        let snapshot = RatesSnapshot::new(vec![latest_rates("USD", &[("USD", "1"), ("PLN", "4.0"), ("EUR", "0.8")])]);
+
+        snapshot.write(&path).unwrap();
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(json.find("\"EUR\"").unwrap() < json.find("\"PLN\"").unwrap());
+        assert!(json.contains("\"time_last_update_unix\": 1711065601"));
+        assert!(!file::tmp_path(&path).unwrap().exists());
+        assert_eq!(RatesSnapshot::load(&path).unwrap(), snapshot);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn leaves_sibling_tmp_files_alone() {
+        let directory = std::env::temp_dir().join(format!("exchange_rate_api_snapshot_dir_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let snapshot = RatesSnapshot::new(vec![latest_rates("USD", &[("USD", "1")])]);
+        fs::write(directory.join("rates.tmp"), "keep").unwrap();
+
+        snapshot.write(&directory.join("rates.json")).unwrap();
+        snapshot.write(&directory.join("x.tmp")).unwrap();
+        assert_eq!(fs::read_to_string(directory.join("rates.tmp")).unwrap(), "keep");
+        assert_eq!(RatesSnapshot::load(&directory.join("x.tmp")).unwrap(), snapshot);
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let json = r#"{"version": 2, "created_unix": 1711065601, "rates": []}"#;
+        assert!(matches!(RatesSnapshot::from_json(json), Err(ExchangeRateError::InvalidResponse(_))));
+    }
+}
@@ -1,13 +1,14 @@
+use exchange_rate_api::currency::CurrencyCode;
+use exchange_rate_api::error::ExchangeRateError;
 use exchange_rate_api::request_handler::{ApiRequest, ConversionRates, StandardRequest, SupportedCodes};
 use rust_decimal::Decimal;
-use tokio;
-use std::env;
+use std::str::FromStr;
 
 #[tokio::test]
 async fn test_fetch_with_invalid_api_key() {
     let invalid_api_key = "invalid_key".to_string();
-    let usd = "USD".to_string();
-    let eur= "EUR".to_string();
+    let usd = CurrencyCode::from_str("USD").unwrap();
+    let eur = CurrencyCode::from_str("EUR").unwrap();
     let dec = Decimal::new(1,0);
 
     let prefix = "https://v6.exchangerate-api.com/".to_string();
@@ -25,25 +26,13 @@ async fn test_fetch_with_invalid_api_key() {
     assert!(conversions_codes_result.is_err(), "Expected an error for the invalid API key");
 }
 
-#[tokio::test]
-async fn test_fetch_with_malformed_request(){
-
-    let api_key = match env::var_os("API_KEY")
-            {
-                Some(_str) => _str.into_string().unwrap(),
-                None => panic!("$API_KEY is not set")
-            };
-    let usd = "USDD".to_string();
-    let eur= "EUR".to_string();
-    let dec = Decimal::new(1,0);
-
-    let prefix = "https://v6.exchangerate-api.com/".to_string();
-    let standard_request = StandardRequest::new(&usd, &eur, &dec, &api_key, &prefix);
-    let standard_request_result = standard_request.fetch().await;
-    assert!(standard_request_result.is_err(), "Malformed request.");
-
-    let conversion_rates_request =  ConversionRates::new(&usd, &api_key, &prefix);
-    let conversion_rates_result = conversion_rates_request.fetch().await;
-    assert!(conversion_rates_result.is_err(), "Malformed request.");
-
+#[test]
+fn test_malformed_currency_code_is_rejected_before_request(){
+    for code in ["USDD", "US", "U$D"] {
+        match CurrencyCode::from_str(code) {
+            Err(ExchangeRateError::InvalidCurrencyCode(invalid_code)) => assert_eq!(invalid_code, code),
+            Err(e) => panic!("Expected InvalidCurrencyCode, got: {}", e),
+            Ok(_) => panic!("{} should not be accepted as a currency code", code),
+        }
+    }
 }